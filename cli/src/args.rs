@@ -77,7 +77,7 @@ enum TaskInner {
 }
 
 fn load_env() -> CliResult<TaskInner> {
-    let action = libsky::parse_cli_args_disallow_duplicate()?;
+    let action = libsky::parse_cli_args_disallow_duplicate(&[])?;
     match action {
         CliAction::Help => Ok(TaskInner::HelpMsg(TXT_HELP.into())),
         CliAction::Version => Ok(TaskInner::HelpMsg(libsky::version_msg("skysh"))),
@@ -32,11 +32,13 @@ use {
         util::{self},
         HarnessError, HarnessResult, ROOT_DIR,
     },
+    libsky::test_utils,
     std::{
         cell::RefCell,
         io::ErrorKind,
         path::Path,
         process::{Child, Command, Output, Stdio},
+        time::Duration,
     },
 };
 
@@ -111,6 +113,8 @@ pub(super) const SERVERS: [(&str, [u16; 2]); 1] = [("server1", [2003, 2004])];
 const TESTSUITE_SERVER_HOST: &str = "127.0.0.1";
 /// The workspace root
 const WORKSPACE_ROOT: &str = env!("ROOT_DIR");
+/// How long we wait for a server to start accepting connections
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(120);
 
 fn connect_db(host: &str, port: u16) -> std::io::Result<std::net::TcpStream> {
     let tcp_stream = std::net::TcpStream::connect((host, port))?;
@@ -181,22 +185,20 @@ fn wait_for_startup() -> HarnessResult<()> {
     for (_, ports) in SERVERS {
         for port in ports {
             let connection_string = format!("{TESTSUITE_SERVER_HOST}:{port}");
-            let mut backoff = 1;
-            let mut con = connect_db(TESTSUITE_SERVER_HOST, port);
-            while connection_refused(con)? {
-                if backoff > 64 {
-                    // enough sleeping, return an error
-                    error!("Server didn't respond in {backoff} seconds. Something is wrong");
-                    return Err(HarnessError::Other(format!(
-                        "Startup backoff elapsed. Server at {connection_string} did not respond."
-                    )));
-                }
-                info!(
-                "Server at {connection_string} not started. Sleeping for {backoff} second(s) ..."
-            );
-                util::sleep_sec(backoff);
-                con = connect_db(TESTSUITE_SERVER_HOST, port);
-                backoff *= 2;
+            let started = test_utils::wait_for_server(TESTSUITE_SERVER_HOST, port, STARTUP_TIMEOUT)
+                .map_err(|e| {
+                    HarnessError::Other(format!(
+                        "Expected ConnectionRefused while checking for startup. Got error {e} instead"
+                    ))
+                })?;
+            if !started {
+                error!(
+                    "Server didn't respond in {} seconds. Something is wrong",
+                    STARTUP_TIMEOUT.as_secs()
+                );
+                return Err(HarnessError::Other(format!(
+                    "Startup timeout elapsed. Server at {connection_string} did not respond."
+                )));
             }
             info!("Server at {connection_string} has started");
         }
@@ -0,0 +1,62 @@
+/*
+ * Created on Sat Oct 17 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+#![no_main]
+
+use {
+    libfuzzer_sys::fuzz_target,
+    regex::Regex,
+    std::{collections::HashMap, sync::OnceLock},
+};
+
+/// arguments that the fuzzed templates can refer to
+const ARGUMENTS: [(&str, &str); 3] = [("binary", "skysh"), ("port", "2003"), ("empty", "")];
+/// arguments that are filled in by `format` itself in `auto` mode
+const AUTO_ARGUMENTS: [&str; 4] = [
+    "default_tcp_endpoint",
+    "default_tls_endpoint",
+    "password_env_var",
+    "version",
+];
+
+fn placeholders() -> &'static Regex {
+    static PLACEHOLDERS: OnceLock<Regex> = OnceLock::new();
+    PLACEHOLDERS.get_or_init(|| Regex::new(r"\{[a-zA-Z_][a-zA-Z_0-9]*\}|\{\}").unwrap())
+}
+
+fuzz_target!(|input: (&str, bool)| {
+    let (body, auto) = input;
+    let arguments: HashMap<&'static str, &'static str> = ARGUMENTS.into_iter().collect();
+    // `format` panics by design on an empty format (`{}`) or on an unknown argument; skip those templates
+    let expect_panic = placeholders().find_iter(body).any(|placeholder| {
+        let name = &placeholder.as_str()[1..placeholder.len() - 1];
+        name.is_empty()
+            || !(arguments.contains_key(name) || (auto && AUTO_ARGUMENTS.contains(&name)))
+    });
+    if !expect_panic {
+        let _ = libsky::format(body, arguments, auto);
+    }
+});
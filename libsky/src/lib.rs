@@ -42,10 +42,51 @@ pub mod env_vars {
 }
 
 pub mod test_utils {
+    #[cfg(any(test, feature = "test-passwords"))]
+    use rand::{distributions::Alphanumeric, rngs::SmallRng, Rng, SeedableRng};
+    use std::{
+        io::{self, ErrorKind},
+        net::TcpStream,
+        thread,
+        time::{Duration, Instant},
+    };
     pub const DEFAULT_USER_NAME: &str = "root";
     pub const DEFAULT_USER_PASS: &str = "mypassword12345678";
     pub const DEFAULT_HOST: &str = "127.0.0.1";
     pub const DEFAULT_PORT: u16 = 2003;
+    #[cfg(any(test, feature = "test-passwords"))]
+    /// the length of passwords generated by [`generate_test_password`] (the server requires atleast 16 characters)
+    const TEST_PASSWORD_LEN: usize = 24;
+    #[cfg(any(test, feature = "test-passwords"))]
+    /// Generate a password that satisfies the server's password policy. The same seed always returns the same password
+    pub fn generate_test_password(seed: u64) -> String {
+        SmallRng::seed_from_u64(seed)
+            .sample_iter(&Alphanumeric)
+            .take(TEST_PASSWORD_LEN)
+            .map(char::from)
+            .collect()
+    }
+    /// Keep trying to connect to the server (every 50ms) until it accepts connections. Returns false if the server
+    /// didn't start accepting connections within `timeout`. Any error other than the connection being refused (or
+    /// reset) is returned immediately
+    pub fn wait_for_server(host: &str, port: u16, timeout: Duration) -> io::Result<bool> {
+        let start = Instant::now();
+        loop {
+            match TcpStream::connect((host, port)) {
+                Ok(_) => return Ok(true),
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        ErrorKind::ConnectionRefused | ErrorKind::ConnectionReset
+                    ) => {}
+                Err(e) => return Err(e),
+            }
+            if start.elapsed() >= timeout {
+                return Ok(false);
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
 }
 
 use std::{
@@ -58,6 +99,27 @@ pub fn version_msg(binary: &str) -> String {
     format!("{binary} v{VERSION}")
 }
 
+/// Returns the current version as a `(major, minor, patch)` tuple. If the version can't be parsed, `(0, 0, 0)` is
+/// returned
+pub fn version_semver() -> (u32, u32, u32) {
+    parse_semver(VERSION).unwrap_or((0, 0, 0))
+}
+
+fn parse_semver(version: &str) -> Option<(u32, u32, u32)> {
+    // ignore any pre-release (`-beta.2`) or build metadata (`+abcdef`)
+    let version = version.split(['-', '+']).next()?;
+    let mut components = version.split('.').map(str::parse);
+    match (
+        components.next()?,
+        components.next()?,
+        components.next()?,
+        components.next(),
+    ) {
+        (Ok(major), Ok(minor), Ok(patch), None) => Some((major, minor, patch)),
+        _ => None,
+    }
+}
+
 #[derive(Debug, PartialEq)]
 /// The CLI action that is expected to be performed
 pub enum CliAction<A> {
@@ -82,13 +144,14 @@ pub enum AnyArgsParseError {
     /// The value for the given argument was either incorrectly formatted or missing
     MissingValue(String),
 }
-/// Parse CLI args, allowing duplicates (bucketing them)
-pub fn parse_cli_args_allow_duplicate() -> Result<CliActionMulti, AnyArgsParseError> {
-    parse_args(env::args())
+/// Parse CLI args, allowing duplicates (bucketing them). `flags` are the boolean flags which don't take a value
+pub fn parse_cli_args_allow_duplicate(flags: &[&str]) -> Result<CliActionMulti, AnyArgsParseError> {
+    parse_args(env::args(), flags)
 }
-/// Parse args allowing and bucketing any duplicates
+/// Parse args allowing and bucketing any duplicates. `flags` are the boolean flags which don't take a value
 pub fn parse_args(
     args: impl IntoIterator<Item = String>,
+    flags: &[&str],
 ) -> Result<CliActionMulti, AnyArgsParseError> {
     let mut ret: HashMap<String, Vec<String>> = HashMap::new();
     let mut args = args.into_iter().skip(1).peekable();
@@ -99,7 +162,8 @@ pub fn parse_args(
         if arg == "--version" {
             return Ok(CliAction::Version);
         }
-        let (arg, value) = extract_arg(arg, &mut args).map_err(AnyArgsParseError::MissingValue)?;
+        let (arg, value) =
+            extract_arg(arg, &mut args, flags).map_err(AnyArgsParseError::MissingValue)?;
         match ret.get_mut(&arg) {
             Some(values) => {
                 values.push(value);
@@ -124,13 +188,14 @@ pub enum ArgParseError {
     /// The given argument did not have an appropriate value
     MissingValue(String),
 }
-/// Parse all non-repeating CLI arguments
-pub fn parse_cli_args_disallow_duplicate() -> Result<CliActionSingle, ArgParseError> {
-    parse_args_deny_duplicate(env::args())
+/// Parse all non-repeating CLI arguments. `flags` are the boolean flags which don't take a value
+pub fn parse_cli_args_disallow_duplicate(flags: &[&str]) -> Result<CliActionSingle, ArgParseError> {
+    parse_args_deny_duplicate(env::args(), flags)
 }
-/// Parse all arguments but deny any duplicates
+/// Parse all arguments but deny any duplicates. `flags` are the boolean flags which don't take a value
 pub fn parse_args_deny_duplicate(
     args: impl IntoIterator<Item = String>,
+    flags: &[&str],
 ) -> Result<CliActionSingle, ArgParseError> {
     let mut ret: HashMap<String, String> = HashMap::new();
     let mut args = args.into_iter().skip(1).peekable();
@@ -141,7 +206,8 @@ pub fn parse_args_deny_duplicate(
         if arg == "--version" {
             return Ok(CliAction::Version);
         }
-        let (arg, value) = extract_arg(arg, &mut args).map_err(ArgParseError::MissingValue)?;
+        let (arg, value) =
+            extract_arg(arg, &mut args, flags).map_err(ArgParseError::MissingValue)?;
         match ret.entry(arg) {
             Entry::Vacant(v) => {
                 v.insert(value);
@@ -155,9 +221,11 @@ pub fn parse_args_deny_duplicate(
 /// Extract an argument:
 /// - `--arg=value`
 /// - `--arg value`
+/// - `--flag` (only for the given boolean `flags`), which is treated as `--flag=true`
 fn extract_arg(
     arg: String,
     args: &mut impl Iterator<Item = String>,
+    flags: &[&str],
 ) -> Result<(String, String), String> {
     let this_args: Vec<&str> = arg.split("=").collect();
     let (arg, value) = if this_args.len() == 2 {
@@ -165,9 +233,13 @@ fn extract_arg(
         (this_args[0].to_owned(), this_args[1].to_owned())
     } else {
         if this_args.len() == 1 {
-            match args.next() {
-                None => return Err(arg),
-                Some(val) => (arg, val),
+            if flags.contains(&arg.as_str()) {
+                (arg, "true".to_owned())
+            } else {
+                match args.next() {
+                    None => return Err(arg),
+                    Some(val) => (arg, val),
+                }
             }
         } else {
             return Err(arg);
@@ -211,6 +283,7 @@ pub mod build_scripts {
         fs::{self, File},
         io::{self, Write},
         path::Path,
+        process::Command,
     };
     pub fn format_help_txt(
         binary_name: &str,
@@ -226,4 +299,120 @@ pub mod build_scripts {
         f.write_all(content.as_bytes())?;
         Ok(())
     }
+    /// Write the short hash of the current git commit to `OUT_DIR/git_hash` (`unknown` if we're not building from
+    /// a git checkout). Include it with `include_str!(concat!(env!("OUT_DIR"), "/git_hash"))`
+    pub fn embed_git_commit_hash() -> io::Result<()> {
+        fn git(args: &[&str]) -> Option<String> {
+            let output = Command::new("git").args(args).output().ok()?;
+            if !output.status.success() {
+                return None;
+            }
+            String::from_utf8(output.stdout)
+                .ok()
+                .map(|s| s.trim().to_owned())
+        }
+        let hash = git(&["rev-parse", "--short", "HEAD"]).unwrap_or("unknown".into());
+        if let Some(git_dir) = git(&["rev-parse", "--git-dir"]) {
+            println!("cargo:rerun-if-changed={git_dir}/HEAD");
+        }
+        let out_dir = env::var("OUT_DIR").unwrap();
+        let dest_path = Path::new(&out_dir).join("git_hash");
+        fs::write(dest_path, hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{parse_args_deny_duplicate, test_utils, ArgParseError, CliAction},
+        std::{net::TcpListener, time::Duration},
+    };
+
+    fn parse(args: &[&str]) -> Result<Vec<(String, String)>, ArgParseError> {
+        let args = ["binary"].iter().chain(args).map(|arg| arg.to_string());
+        match parse_args_deny_duplicate(args, &["--flag"])? {
+            CliAction::Action(args) => {
+                let mut args: Vec<_> = args.into_iter().collect();
+                args.sort();
+                Ok(args)
+            }
+            _ => panic!("expected action"),
+        }
+    }
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parse_flag() {
+        assert_eq!(
+            parse(&["--flag", "--arg", "val"]),
+            Ok(pairs(&[("--arg", "val"), ("--flag", "true")]))
+        );
+    }
+
+    #[test]
+    fn parse_flag_last() {
+        assert_eq!(
+            parse(&["--arg", "val", "--flag"]),
+            Ok(pairs(&[("--arg", "val"), ("--flag", "true")]))
+        );
+    }
+
+    #[test]
+    fn parse_arg_with_value() {
+        assert_eq!(
+            parse(&["--arg", "val", "--other=val2"]),
+            Ok(pairs(&[("--arg", "val"), ("--other", "val2")]))
+        );
+    }
+
+    #[test]
+    fn parse_arg_followed_by_arg() {
+        // a value-taking argument takes the next token, even if it looks like an argument
+        assert_eq!(
+            parse(&["--arg", "--other"]),
+            Ok(pairs(&[("--arg", "--other")]))
+        );
+        assert_eq!(
+            parse(&["--arg"]),
+            Err(ArgParseError::MissingValue("--arg".into()))
+        );
+    }
+
+    #[test]
+    fn parse_semver() {
+        assert_eq!(super::parse_semver("0.8.0"), Some((0, 8, 0)));
+        assert_eq!(super::parse_semver("0.8.0-beta.2"), Some((0, 8, 0)));
+        assert_eq!(super::parse_semver("0.8.1+a1b2c3"), Some((0, 8, 1)));
+        assert_eq!(super::parse_semver("1.2.3-rc.1+a1b2c3"), Some((1, 2, 3)));
+        assert_eq!(super::parse_semver("0.8"), None);
+        assert_eq!(super::parse_semver("0.8.0.1"), None);
+        assert_eq!(super::parse_semver("0.x.0"), None);
+    }
+
+    #[test]
+    fn wait_for_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(test_utils::wait_for_server("127.0.0.1", port, Duration::from_secs(10)).unwrap());
+        drop(listener);
+        assert!(
+            !test_utils::wait_for_server("127.0.0.1", port, Duration::from_millis(200)).unwrap()
+        );
+    }
+    #[test]
+    fn generate_test_password() {
+        let password = test_utils::generate_test_password(1);
+        // the server rejects passwords shorter than 16 characters
+        assert!(password.len() >= 16);
+        assert!(password.chars().all(|c| c.is_ascii_alphanumeric()));
+        // same seed, same password
+        assert_eq!(password, test_utils::generate_test_password(1));
+        assert_ne!(password, test_utils::generate_test_password(2));
+    }
 }
@@ -27,7 +27,7 @@
 use {
     crate::engine::{error::RuntimeResult, fractal},
     core::fmt,
-    serde::Deserialize,
+    serde::{Deserialize, Serialize},
     std::{collections::HashMap, fs},
 };
 
@@ -108,6 +108,100 @@ impl Configuration {
             auth: ConfigAuth::new(auth.plugin, auth.root_pass),
         }
     }
+    /// Export the configuration as YAML (in the same layout as the configuration file) with all secrets
+    /// redacted
+    pub fn to_yaml(&self) -> String {
+        const REDACTED: &str = "<redacted>";
+        #[derive(Serialize)]
+        struct Export<'a> {
+            system: ExportSystem,
+            auth: ExportAuth,
+            endpoints: ExportEndpoints<'a>,
+        }
+        #[derive(Serialize)]
+        struct ExportSystem {
+            mode: ConfigMode,
+            rs_window: u64,
+        }
+        #[derive(Serialize)]
+        struct ExportAuth {
+            plugin: AuthDriver,
+            root_pass: &'static str,
+        }
+        #[derive(Serialize)]
+        struct ExportEndpoints<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            secure: Option<ExportSecureEndpoint<'a>>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            insecure: Option<ExportInsecureEndpoint<'a>>,
+        }
+        #[derive(Serialize)]
+        struct ExportSecureEndpoint<'a> {
+            host: &'a str,
+            port: u16,
+            cert: &'static str,
+            private_key: &'static str,
+            pkey_passphrase: &'static str,
+        }
+        #[derive(Serialize)]
+        struct ExportInsecureEndpoint<'a> {
+            host: &'a str,
+            port: u16,
+        }
+        fn insecure(tcp: &ConfigEndpointTcp) -> ExportInsecureEndpoint<'_> {
+            ExportInsecureEndpoint {
+                host: tcp.host(),
+                port: tcp.port(),
+            }
+        }
+        fn secure(tls: &ConfigEndpointTls) -> ExportSecureEndpoint<'_> {
+            ExportSecureEndpoint {
+                host: tls.tcp().host(),
+                port: tls.tcp().port(),
+                cert: REDACTED,
+                private_key: REDACTED,
+                pkey_passphrase: REDACTED,
+            }
+        }
+        let endpoints = match &self.endpoints {
+            ConfigEndpoint::Insecure(tcp) => ExportEndpoints {
+                secure: None,
+                insecure: Some(insecure(tcp)),
+            },
+            ConfigEndpoint::Secure(tls) => ExportEndpoints {
+                secure: Some(secure(tls)),
+                insecure: None,
+            },
+            ConfigEndpoint::Multi(tcp, tls) => ExportEndpoints {
+                secure: Some(secure(tls)),
+                insecure: Some(insecure(tcp)),
+            },
+        };
+        serde_yaml::to_string(&Export {
+            system: ExportSystem {
+                mode: self.mode,
+                rs_window: self.system.reliability_system_window,
+            },
+            auth: ExportAuth {
+                plugin: self.auth.plugin,
+                root_pass: REDACTED,
+            },
+            endpoints,
+        })
+        .unwrap()
+    }
+    /// Check invariants that span multiple fields (and hence can't be checked when decoding any single one of
+    /// them)
+    pub fn validate_consistency(&self) -> Result<(), ConfigError> {
+        match &self.endpoints {
+            ConfigEndpoint::Multi(tcp, tls)
+                if tcp.host() == tls.tcp().host() && tcp.port() == tls.tcp().port() =>
+            {
+                Err(ConfigError::new(ConfigErrorKind::EndpointAddressConflict))
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 // endpoint config
@@ -182,7 +276,7 @@ impl ConfigEndpointTls {
     config mode
 */
 
-#[derive(Debug, PartialEq, Deserialize, Clone, Copy)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone, Copy)]
 /// The configuration mode
 pub enum ConfigMode {
     /// In [`ConfigMode::Dev`] we're allowed to be more relaxed with settings
@@ -216,7 +310,7 @@ impl ConfigSystem {
     config auth
 */
 
-#[derive(Debug, PartialEq, Deserialize, Clone, Copy)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone, Copy)]
 pub enum AuthDriver {
     #[serde(rename = "pwd")]
     Pwd,
@@ -246,6 +340,27 @@ pub struct DecodedConfiguration {
     auth: Option<DecodedAuth>,
 }
 
+impl DecodedConfiguration {
+    /// Override the settings in self with every setting that is present in `other`
+    fn overlay(&mut self, other: Self) {
+        if let Some(other_system) = other.system {
+            match self.system.as_mut() {
+                Some(system) => {
+                    system.mode = other_system.mode.or(system.mode);
+                    system.rs_window = other_system.rs_window.or(system.rs_window);
+                }
+                None => self.system = Some(other_system),
+            }
+        }
+        if other.endpoints.is_some() {
+            self.endpoints = other.endpoints;
+        }
+        if other.auth.is_some() {
+            self.auth = other.auth;
+        }
+    }
+}
+
 impl Default for DecodedConfiguration {
     fn default() -> Self {
         Self {
@@ -341,6 +456,10 @@ impl fmt::Display for ConfigError {
                 "conflicting settings. please choose either CLI or ENV or configuration file"
             ),
             ConfigErrorKind::ErrorString(e) => write!(f, "{e}"),
+            ConfigErrorKind::EndpointAddressConflict => write!(
+                f,
+                "the TCP and TLS endpoints cannot listen on the same host and port"
+            ),
         }
     }
 }
@@ -374,6 +493,8 @@ pub enum ConfigErrorKind {
     Conflict,
     /// A custom error output
     ErrorString(String),
+    /// The TCP and TLS endpoints are bound to the same address
+    EndpointAddressConflict,
 }
 
 /// A configuration source implementation
@@ -526,6 +647,10 @@ fn arg_decode_auth<CS: ConfigurationSource>(
     config: &mut ModifyGuard<DecodedConfiguration>,
 ) -> RuntimeResult<()> {
     let auth_driver = src_args.remove(CS::KEY_AUTH_DRIVER);
+    if auth_driver.is_none() & !src_args.contains_key(CS::KEY_AUTH_ROOT_PASSWORD) {
+        // nothing to set here; validation will complain if no other source set up the root account
+        return Ok(());
+    }
     let Some(mut root_key) = src_args.remove(CS::KEY_AUTH_ROOT_PASSWORD) else {
         return Err(ConfigError::with_src(
             CS::SOURCE,
@@ -964,6 +1089,10 @@ fn validate_configuration<CS: ConfigurationSource>(
             ConfigErrorKind::ErrorString("the root password must have at least 16 characters".into()),
         ).into(),
     );
+    // and finally, check invariants across fields
+    config
+        .validate_consistency()
+        .map_err(|e| ConfigError::with_src(CS::SOURCE, e.kind))?;
     Ok(config)
 }
 
@@ -1144,26 +1273,38 @@ pub fn check_configuration() -> RuntimeResult<ConfigReturn> {
     }
 }
 
+/// Decode the raw YAML configuration file
+fn decode_config_file(file: &str) -> RuntimeResult<DecodedConfiguration> {
+    serde_yaml::from_str(file).map_err(|e| {
+        ConfigError::with_src(
+            ConfigSource::File,
+            ConfigErrorKind::ErrorString(format!(
+                "failed to parse YAML config file with error: `{e}`"
+            )),
+        )
+        .into()
+    })
+}
+
+#[cfg(test)]
+/// Decode and validate a YAML configuration held in memory. Unlike [`check_configuration`], the TLS paths are not
+/// read from disk
+pub(super) fn parse_config_str(s: &str) -> RuntimeResult<ConfigReturn> {
+    validate_configuration::<CSConfigFile>(decode_config_file(s)?).map(ConfigReturn::Config)
+}
+
 /// Check the configuration file
 fn check_config_file(
     cfg_from_cli: &ParsedRawArgs,
     env_args: &Option<ParsedRawArgs>,
     cfg_file: &Vec<String>,
 ) -> RuntimeResult<ConfigReturn> {
-    if cfg_from_cli.len() == 1 && env_args.is_none() {
-        // yes, we only have the config file
+    if cfg_from_cli.len() == 1 {
+        // yes, we only have the config file (and maybe some env variables that override it)
         argck_duplicate_values::<CSCommandLine>(&cfg_file, CSCommandLine::ARG_CONFIG_FILE)?;
         // read the config file
         let file = get_file_from_store(&cfg_file[0])?;
-        let mut config_from_file: DecodedConfiguration =
-            serde_yaml::from_str(&file).map_err(|e| {
-                ConfigError::with_src(
-                    ConfigSource::File,
-                    ConfigErrorKind::ErrorString(format!(
-                        "failed to parse YAML config file with error: `{e}`"
-                    )),
-                )
-            })?;
+        let mut config_from_file = decode_config_file(&file)?;
         // read in the TLS certs (if any)
         match config_from_file.endpoints.as_mut() {
             Some(ep) => match ep.secure.as_mut() {
@@ -1180,10 +1321,15 @@ fn check_config_file(
             },
             None => {}
         }
+        // env variables take precedence over the config file
+        if let Some(env_args) = env_args {
+            let from_env = apply_config_changes::<CSEnvArgs>(&mut env_args.clone())?;
+            config_from_file.overlay(from_env.val);
+        }
         // done here
         return validate_configuration::<CSConfigFile>(config_from_file).map(ConfigReturn::Config);
     } else {
-        // so there are more configuration options + a config file? (and maybe even env?)
+        // so there are more CLI options + a config file?
         return Err(ConfigError::with_src(ConfigSource::Cli, ConfigErrorKind::Conflict).into());
     }
 }
@@ -28,7 +28,7 @@ use crate::engine::{
     data::{tag::TagClass, DictEntryGeneric},
     error::{QueryError, QueryResult},
     fractal::GlobalInstanceLike,
-    net::protocol::ClientLocalState,
+    net::protocol::{ClientLocalState, Response, ResponseType},
     ql::dcl::{SysctlCommand, UserDecl, UserDel},
 };
 
@@ -38,7 +38,7 @@ pub fn exec<G: GlobalInstanceLike>(
     g: G,
     current_user: &ClientLocalState,
     cmd: SysctlCommand,
-) -> QueryResult<()> {
+) -> QueryResult<Response> {
     if cmd.needs_root() & !current_user.is_root() {
         return Err(QueryError::SysPermissionDenied);
     }
@@ -53,6 +53,31 @@ pub fn exec<G: GlobalInstanceLike>(
                 Err(QueryError::SysServerError)
             }
         }
+        SysctlCommand::ReportConfig => return Ok(report_config(&g)),
+        SysctlCommand::ReportStats => return Ok(report_stats(&g)),
+    }
+    .map(|_| Response::Empty)
+}
+
+fn report_config(global: &impl GlobalInstanceLike) -> Response {
+    let cfg = global.config_export().to_owned();
+    Response::Serialized {
+        ty: ResponseType::String,
+        size: cfg.len(),
+        data: cfg.into_bytes(),
+    }
+}
+
+fn report_stats(global: &impl GlobalInstanceLike) -> Response {
+    let stats = format!(
+        "{{\"active_connections\":{},\"uptime_secs\":{}}}",
+        global.active_connection_count(),
+        global.uptime().as_secs()
+    );
+    Response::Serialized {
+        ty: ResponseType::String,
+        size: stats.len(),
+        data: stats.into_bytes(),
     }
 }
 
@@ -192,7 +192,7 @@ fn blocking_exec_sysctl(
     state: &mut State<'static, InplaceData>,
 ) -> QueryResult<Response> {
     let r = ASTNode::parse_from_state_hardened(state)?;
-    super::dcl::exec(g, cstate, r).map(|_| Response::Empty)
+    super::dcl::exec(g, cstate, r)
 }
 
 /*
@@ -127,19 +127,23 @@ macro_rules! enumerate_err {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, sky_macros::ErrorKind)]
 #[cfg_attr(test, derive(PartialEq))]
 /// A "master" error kind enumeration for all kinds of runtime errors
 pub enum ErrorKind {
     /// An I/O error
     IoError(SysIOError),
     /// An SDSS error
+    #[error_kind(skip)] // NB: has a dedicated cast into `Error` that pops the local context
     Storage(StorageError),
     /// A transactional error
+    #[error_kind(skip)] // NB: has a dedicated cast into `Error` that pops the local context
     Txn(TransactionError),
     /// other errors
+    #[error_kind(skip)]
     Other(String),
     /// configuration errors
+    #[error_kind(skip)] // NB: has a dedicated cast into `Error` that sets the origin
     Config(ConfigError),
 }
 
@@ -160,7 +164,6 @@ impl std::error::Error for ErrorKind {}
 direct_from! {
     ErrorKind => {
         std::io::Error as IoError,
-        SysIOError as IoError,
     }
 }
 
@@ -223,3 +226,48 @@ enumerate_err! {
         RawJournalRuntimeCriticalLwtHBFail = "journal-lwt-heartbeat-failed",
     }
 }
+
+impl StorageError {
+    /// Returns a user-facing explanation of the error along with common remediation steps
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::HeaderDecodeVersionMismatch => {
+                "a data file was written by an incompatible version of Skytable. upgrade (or migrate) the data files before starting this version"
+            }
+            Self::HeaderDecodeCorruptedHeader => {
+                "the header of a data file is corrupted. restore the file from a backup"
+            }
+            Self::JournalLogEntryCorrupted => {
+                "an entry in a journal is corrupted, possibly due to a crash or disk failure. restore the journal from a backup"
+            }
+            Self::JournalCorrupted | Self::RawJournalCorrupted => {
+                "the structure of a journal is corrupted, possibly due to a crash or disk failure. restore the journal from a backup"
+            }
+            Self::InternalDecodeStructureCorrupted
+            | Self::InternalDecodeStructureCorruptedPayload => {
+                "an internal structure in a data file is corrupted. restore the file from a backup"
+            }
+            Self::InternalDecodeStructureIllegalData => {
+                "a data file contains logically invalid data, possibly due to a bug or manual modification. restore the file from a backup"
+            }
+            Self::DataBatchRestoreCorruptedBatch
+            | Self::DataBatchRestoreCorruptedEntry
+            | Self::DataBatchRestoreCorruptedBatchFile => {
+                "a model's data batch file is corrupted, possibly due to a crash or disk failure. restore the model's data from a backup"
+            }
+            Self::DataBatchCloseError => {
+                "a model's data batch file could not be closed. check that the disk is writable and has free space"
+            }
+            Self::SysDBCorrupted => "the system database is corrupted. restore it from a backup",
+            Self::RawJournalEventCorruptedMetadata | Self::RawJournalEventCorrupted => {
+                "an event in a journal is corrupted, possibly due to a crash or disk failure. restore the journal from a backup"
+            }
+            Self::RawJournalInvalidEvent => {
+                "a journal contains events in an invalid order, possibly due to manual modification. restore the journal from a backup"
+            }
+            Self::RawJournalRuntimeCriticalLwtHBFail => {
+                "a journal heartbeat could not be written. check that the disk is writable and has free space"
+            }
+        }
+    }
+}
@@ -194,3 +194,105 @@ impl LocalContext {
         local_mut!(CTX, f)
     }
 }
+
+/*
+    spans
+*/
+
+/// A single frame in the local span chain
+#[derive(Debug, PartialEq, Clone)]
+pub struct ContextFrame {
+    msg: &'static str,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl ContextFrame {
+    pub fn new(msg: &'static str, fields: Vec<(&'static str, String)>) -> Self {
+        Self { msg, fields }
+    }
+    pub fn msg(&self) -> &'static str {
+        self.msg
+    }
+    pub fn fields(&self) -> &[(&'static str, String)] {
+        &self.fields
+    }
+}
+
+impl fmt::Display for ContextFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "while {}", self.msg)?;
+        let mut fields = self.fields.iter();
+        if let Some((key, value)) = fields.next() {
+            write!(f, " ({key}={value}")?;
+            for (key, value) in fields {
+                write!(f, ", {key}={value}")?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+struct LocalSpans;
+
+impl LocalSpans {
+    fn _spans<T>(f: impl FnOnce(&mut Vec<ContextFrame>) -> T) -> T {
+        local! { static SPANS: Vec<ContextFrame> = Vec::new(); }
+        local_mut!(SPANS, f)
+    }
+    /// render the chain and set it as the local dmsg so that any error created in this span inherits it
+    fn _sync(spans: &[ContextFrame]) {
+        if spans.is_empty() {
+            LocalContext::pop_dmsg();
+        } else {
+            LocalContext::set_dmsg(Self::_render(spans));
+        }
+    }
+    fn _render(spans: &[ContextFrame]) -> String {
+        spans
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" → ")
+    }
+}
+
+/// Begin a new operation in the given subsystem. This resets the local span chain and makes `frame` its root
+pub fn span_reset(origin: Subsystem, frame: ContextFrame) {
+    LocalContext::set_origin(origin);
+    LocalSpans::_spans(|spans| {
+        spans.clear();
+        spans.push(frame);
+        LocalSpans::_sync(spans)
+    })
+}
+
+/// Enter a nested span in the current operation. The span is exited (popped off the chain) once the returned guard
+/// is dropped
+pub fn span_enter(frame: ContextFrame) -> SpanGuard {
+    LocalSpans::_spans(|spans| {
+        spans.push(frame);
+        LocalSpans::_sync(spans)
+    });
+    SpanGuard { _private: () }
+}
+
+/// A guard for a span entered with [`span_enter`]
+#[must_use = "the span is exited as soon as the guard is dropped"]
+pub struct SpanGuard {
+    _private: (),
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        LocalSpans::_spans(|spans| {
+            spans.pop();
+            LocalSpans::_sync(spans)
+        })
+    }
+}
+
+/// Get the rendered span chain of the current operation (if any)
+pub fn span_chain() -> Option<String> {
+    LocalSpans::_spans(|spans| (!spans.is_empty()).then(|| LocalSpans::_render(spans)))
+}
@@ -66,14 +66,14 @@ impl FractalGNSDriver {
         let mut txn_driver = self.txn_driver.lock();
         match f(&mut txn_driver) {
             Ok(v) => Ok(v),
-            Err(e) => compiler::cold_call(|| {
+            Err(e) => compiler::cold_call(compiler::cold_box(|| {
                 self.status.set_iffy();
                 g.health().report_fault();
                 on_failure();
                 g.taskmgr_post_high_priority(Task::new(CriticalTask::CheckGNSDriver));
                 error!("GNS driver failed with: {e}");
                 Err(QueryError::SysServerError)
-            }),
+            })),
         }
     }
 }
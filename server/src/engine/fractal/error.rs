@@ -30,7 +30,7 @@ use {
         config::ConfigError,
         error::{ErrorKind, StorageError, TransactionError},
     },
-    core::fmt,
+    core::{fmt, panic::Location},
 };
 
 #[derive(Debug)]
@@ -48,6 +48,10 @@ pub struct Error {
     kind: ErrorKind,
     origin: Option<Subsystem>,
     dmsg: Option<Dmsg>,
+    /// the source location this error was attached to (see [`ErrorContext::with_location`]); only tracked in
+    /// debug builds
+    #[cfg(debug_assertions)]
+    location: Option<&'static Location<'static>>,
 }
 
 impl Error {
@@ -55,20 +59,47 @@ impl Error {
     pub fn kind(&self) -> &ErrorKind {
         &self.kind
     }
+    /// Returns the storage error, if this is one
+    pub fn downcast_storage(&self) -> Option<&StorageError> {
+        match &self.kind {
+            ErrorKind::Storage(se) => Some(se),
+            _ => None,
+        }
+    }
+    /// Returns the transactional error, if this is one
+    #[allow(unused)]
+    pub fn downcast_txn(&self) -> Option<&TransactionError> {
+        match &self.kind {
+            ErrorKind::Txn(txn) => Some(txn),
+            _ => None,
+        }
+    }
+    /// Returns a user-facing explanation of the error, if one is available
+    pub fn description(&self) -> Option<&'static str> {
+        self.downcast_storage().map(StorageError::description)
+    }
     /// Replace the origin in self
-    pub fn add_origin(self, origin: Subsystem) -> Self {
-        Self::_new(self.kind, Some(origin), self.dmsg)
+    pub fn add_origin(mut self, origin: Subsystem) -> Self {
+        self.origin = Some(origin);
+        self
     }
     /// Replace the dmsg in self
-    pub fn add_dmsg(self, dmsg: impl Into<Dmsg>) -> Self {
-        Self::_new(self.kind, self.origin, Some(dmsg.into()))
+    pub fn add_dmsg(mut self, dmsg: impl Into<Dmsg>) -> Self {
+        self.dmsg = Some(dmsg.into());
+        self
     }
 }
 
 impl Error {
     /// ctor
     fn _new(kind: ErrorKind, origin: Option<Subsystem>, dmsg: Option<Dmsg>) -> Self {
-        Self { kind, origin, dmsg }
+        Self {
+            kind,
+            origin,
+            dmsg,
+            #[cfg(debug_assertions)]
+            location: None,
+        }
     }
     /// new full error
     pub fn new(kind: ErrorKind, origin: Subsystem, dmsg: impl Into<Dmsg>) -> Self {
@@ -83,12 +114,14 @@ impl Error {
         Self::_new(kind, Some(origin), None)
     }
     /// remove the dmsg from self
-    fn remove_dmsg(self) -> Self {
-        Self::_new(self.kind, self.origin, None)
+    fn remove_dmsg(mut self) -> Self {
+        self.dmsg = None;
+        self
     }
     /// remove the origin from self
-    fn remove_origin(self) -> Self {
-        Self::_new(self.kind, None, self.dmsg)
+    fn remove_origin(mut self) -> Self {
+        self.origin = None;
+        self
     }
 }
 
@@ -102,7 +135,14 @@ impl fmt::Display for Error {
             Some(dmsg) => write!(f, "{dmsg}; ")?,
             None => {}
         }
-        write!(f, "{}", self.kind)
+        write!(f, "{}", self.kind)?;
+        #[cfg(debug_assertions)]
+        {
+            if let Some(location) = self.location {
+                write!(f, " (at {location})")?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -189,6 +229,10 @@ pub trait ErrorContext<T> {
     fn orphan_origin(self) -> Result<T, Error>;
     /// orphan the dmsg (if any)
     fn orphan_dmsg(self) -> Result<T, Error>;
+    // debug
+    /// attach the caller's source location (inherit rest from parent). locations are only tracked in debug builds
+    #[track_caller]
+    fn with_location(self) -> Result<T, Error>;
 }
 
 impl<T, E> ErrorContext<T> for Result<T, E>
@@ -270,6 +314,23 @@ where
     fn orphan_origin(self) -> Result<T, Error> {
         self.map_err(|e| e.err_inherit_parent().remove_origin())
     }
+    #[track_caller]
+    fn with_location(self) -> Result<T, Error> {
+        let location = Location::caller();
+        self.map_err(|e| {
+            #[allow(unused_mut)]
+            let mut e = e.err_inherit_parent();
+            #[cfg(debug_assertions)]
+            {
+                e.location = Some(location);
+            }
+            #[cfg(not(debug_assertions))]
+            {
+                let _ = location;
+            }
+            e
+        })
+    }
 }
 
 /*
@@ -37,7 +37,12 @@ use {
     std::{
         fmt,
         mem::MaybeUninit,
-        sync::atomic::{AtomicUsize, Ordering},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc::{self, RecvTimeoutError},
+        },
+        thread,
+        time::{Duration, Instant},
     },
     tokio::sync::mpsc::unbounded_channel,
 };
@@ -48,11 +53,13 @@ pub mod error;
 mod mgr;
 #[cfg(test)]
 pub mod test_utils;
+#[cfg(test)]
+mod tests;
 mod util;
 pub use {
     drivers::{FractalGNSDriver, FractalModelDriver},
     mgr::{CriticalTask, GenericTask, Task, GENERAL_EXECUTOR_WINDOW},
-    util::FractalToken,
+    util::{FractalToken, ShutdownToken},
 };
 
 /*
@@ -71,13 +78,14 @@ pub struct GlobalStateStart {
 /// ## Safety
 ///
 /// Must be called iff this is the only thread calling it
-pub unsafe fn load_and_enable_all(gns: GlobalNS) -> GlobalStateStart {
+pub unsafe fn load_and_enable_all(gns: GlobalNS, config_export: String) -> GlobalStateStart {
     let model_cnt_on_boot = gns.namespace().idx_models().read().len();
     let (hp_sender, hp_recv) = unbounded_channel();
     let (lp_sender, lp_recv) = unbounded_channel();
     let global_state = GlobalState::new(
         gns,
         mgr::FractalMgr::new(hp_sender, lp_sender, model_cnt_on_boot),
+        config_export,
     );
     *Global::__gref_raw() = MaybeUninit::new(global_state);
     let token = Global::new();
@@ -120,6 +128,10 @@ pub trait GlobalInstanceLike {
     // stat
     fn health(&self) -> &GlobalHealth;
     fn get_max_delta_size(&self) -> usize;
+    /// Returns the exported (and redacted) configuration that the server is running with
+    fn config_export(&self) -> &str;
+    fn active_connection_count(&self) -> usize;
+    fn uptime(&self) -> Duration;
     // global namespace
     fn state(&self) -> &GlobalNS;
     fn initialize_space(&self, space_name: &str, space_uuid: Uuid) -> RuntimeResult<()> {
@@ -197,6 +209,15 @@ impl GlobalInstanceLike for Global {
     fn get_max_delta_size(&self) -> usize {
         self._get_max_delta_size()
     }
+    fn config_export(&self) -> &str {
+        &self.get_state().config_export
+    }
+    fn active_connection_count(&self) -> usize {
+        self.get_state().active_connections.load(Ordering::Acquire)
+    }
+    fn uptime(&self) -> Duration {
+        self.get_state().started_at.elapsed()
+    }
     // model
     fn purge_model_driver(
         &self,
@@ -262,6 +283,22 @@ impl Global {
             .get_rt_stat()
             .per_mdl_delta_max_size()
     }
+    /// Returns a token that can be used to request a clean shutdown of the server
+    pub fn shutdown_token(&self) -> ShutdownToken {
+        self.get_state().shutdown.clone()
+    }
+    /// Record a newly accepted client connection
+    pub fn connection_opened(&self) {
+        self.get_state()
+            .active_connections
+            .fetch_add(1, Ordering::Release);
+    }
+    /// Record a closed client connection
+    pub fn connection_closed(&self) {
+        self.get_state()
+            .active_connections
+            .fetch_sub(1, Ordering::Release);
+    }
     unsafe fn __gref_raw() -> &'static mut MaybeUninit<GlobalState> {
         static mut G: MaybeUninit<GlobalState> = MaybeUninit::uninit();
         &mut G
@@ -269,19 +306,97 @@ impl Global {
     unsafe fn __gref(&self) -> &'static GlobalState {
         Self::__gref_raw().assume_init_ref()
     }
-    pub unsafe fn unload_all(self) {
-        // TODO(@ohsayan): handle errors
-        let GlobalState { gns, .. } = Self::__gref_raw().assume_init_read();
-        let mut gns_driver = gns.gns_driver().txn_driver.lock();
-        GNSDriver::close_driver(&mut gns_driver).unwrap();
-        for mdl in gns
-            .namespace()
-            .idx_models()
-            .write()
-            .drain()
-            .map(|(_, mdl)| mdl)
-        {
-            mdl.into_driver().close().unwrap();
+    /// Unload all storage components, giving each one of them at most `timeout` to close. Returns the components
+    /// that either failed to close or didn't close in time (if a component hangs, it and all remaining components
+    /// are abandoned)
+    pub unsafe fn unload_all(self, timeout: Duration) -> Vec<ComponentError> {
+        let state = Self::__gref_raw().assume_init_read();
+        // the state is moved to a separate closing thread that we never join, so that a hung component can't keep
+        // us from exiting
+        close_with_timeout(timeout, move |tx| {
+            let state = state;
+            let _ = tx.send(UnloadEvent::Closing("gns".into()));
+            let mut gns_driver = state.gns.gns_driver().txn_driver.lock();
+            let _ = tx.send(UnloadEvent::Closed(GNSDriver::close_driver(
+                &mut gns_driver,
+            )));
+            drop(gns_driver);
+            for (id, mdl) in state.gns.namespace().idx_models().write().drain() {
+                let _ = tx.send(UnloadEvent::Closing(format!(
+                    "model {}.{}",
+                    id.space(),
+                    id.entity()
+                )));
+                let _ = tx.send(UnloadEvent::Closed(mdl.into_driver().close()));
+            }
+        })
+    }
+}
+
+/// Run `close` on a new thread and collect the components that it reports. We wait at most `timeout` for every
+/// event; if `close` hangs, the component being closed and all remaining components are abandoned
+fn close_with_timeout(
+    timeout: Duration,
+    close: impl FnOnce(mpsc::Sender<UnloadEvent>) + Send + 'static,
+) -> Vec<ComponentError> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || close(tx));
+    let mut errors = vec![];
+    let mut current = None;
+    loop {
+        match rx.recv_timeout(timeout) {
+            Ok(UnloadEvent::Closing(component)) => current = Some(component),
+            Ok(UnloadEvent::Closed(r)) => {
+                let component = current.take().unwrap();
+                if let Err(e) = r {
+                    errors.push(ComponentError::Failed(component, e));
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                errors.push(ComponentError::TimedOut(
+                    current.unwrap_or_else(|| "storage engine".into()),
+                ));
+                break;
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                // the closing thread either finished or panicked while closing the current component
+                errors.extend(current.map(ComponentError::Panicked));
+                break;
+            }
+        }
+    }
+    errors
+}
+
+enum UnloadEvent {
+    Closing(String),
+    Closed(RuntimeResult<()>),
+}
+
+/// A storage component that couldn't be closed cleanly while unloading
+#[derive(Debug)]
+pub enum ComponentError {
+    /// the component failed to close
+    Failed(String, error::Error),
+    /// the component didn't close in time
+    TimedOut(String),
+    /// the component panicked while closing
+    Panicked(String),
+}
+
+impl ComponentError {
+    /// Returns true if the component was abandoned while it was still closing
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Self::TimedOut(_))
+    }
+}
+
+impl fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed(component, e) => write!(f, "failed to close {component}: {e}"),
+            Self::TimedOut(component) => write!(f, "timed out while closing {component}"),
+            Self::Panicked(component) => write!(f, "panicked while closing {component}"),
         }
     }
 }
@@ -295,14 +410,22 @@ struct GlobalState {
     gns: GlobalNS,
     task_mgr: mgr::FractalMgr,
     health: GlobalHealth,
+    config_export: String,
+    active_connections: AtomicUsize,
+    started_at: Instant,
+    shutdown: ShutdownToken,
 }
 
 impl GlobalState {
-    fn new(gns: GlobalNS, task_mgr: mgr::FractalMgr) -> Self {
+    fn new(gns: GlobalNS, task_mgr: mgr::FractalMgr, config_export: String) -> Self {
         Self {
             gns,
             task_mgr,
             health: GlobalHealth::new(),
+            config_export,
+            active_connections: AtomicUsize::new(0),
+            started_at: Instant::now(),
+            shutdown: ShutdownToken::new(),
         }
     }
     pub(self) fn fractal_mgr(&self) -> &mgr::FractalMgr {
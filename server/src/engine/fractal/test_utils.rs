@@ -40,6 +40,7 @@ use {
         RuntimeResult,
     },
     parking_lot::RwLock,
+    std::time::Duration,
 };
 
 /// A `test` mode global implementation
@@ -142,6 +143,15 @@ impl GlobalInstanceLike for TestGlobal {
     fn get_max_delta_size(&self) -> usize {
         self.max_delta_size
     }
+    fn config_export(&self) -> &str {
+        ""
+    }
+    fn active_connection_count(&self) -> usize {
+        0
+    }
+    fn uptime(&self) -> Duration {
+        Duration::ZERO
+    }
     fn purge_model_driver(
         &self,
         space_name: &str,
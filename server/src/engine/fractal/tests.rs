@@ -0,0 +1,132 @@
+/*
+ * Created on Sat Oct 17 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+use {
+    super::{
+        close_with_timeout,
+        context::{self, Subsystem},
+        error::Error,
+        ComponentError, UnloadEvent,
+    },
+    crate::engine::error::StorageError,
+    std::{thread, time::Duration},
+};
+
+#[test]
+fn close_all_clean() {
+    let errors = close_with_timeout(Duration::from_secs(10), |tx| {
+        for component in ["a", "b"] {
+            let _ = tx.send(UnloadEvent::Closing(component.into()));
+            let _ = tx.send(UnloadEvent::Closed(Ok(())));
+        }
+    });
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn close_abandons_hung_component() {
+    let errors = close_with_timeout(Duration::from_millis(100), |tx| {
+        let _ = tx.send(UnloadEvent::Closing("a".into()));
+        let _ = tx.send(UnloadEvent::Closed(Ok(())));
+        let _ = tx.send(UnloadEvent::Closing("b".into()));
+        thread::sleep(Duration::from_secs(2));
+        let _ = tx.send(UnloadEvent::Closed(Ok(())));
+    });
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&errors[0], ComponentError::TimedOut(c) if c == "b"));
+    assert!(errors[0].is_timeout());
+}
+
+#[test]
+fn close_reports_failed_and_panicked() {
+    let errors = close_with_timeout(Duration::from_secs(10), |tx| {
+        let _ = tx.send(UnloadEvent::Closing("a".into()));
+        let _ = tx.send(UnloadEvent::Closed(Err(
+            StorageError::RawJournalCorrupted.into()
+        )));
+        let _ = tx.send(UnloadEvent::Closing("b".into()));
+        panic!("b is broken");
+    });
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(&errors[0], ComponentError::Failed(c, _) if c == "a"));
+    assert!(matches!(&errors[1], ComponentError::Panicked(c) if c == "b"));
+    assert!(errors.iter().all(|e| !e.is_timeout()));
+}
+
+#[test]
+fn span_render() {
+    assert_eq!(span!("loading gns").to_string(), "while loading gns");
+    assert_eq!(
+        span!("loading model", space = "myspace", model = "mymodel").to_string(),
+        "while loading model (space=myspace, model=mymodel)"
+    );
+}
+
+#[test]
+fn span_nesting() {
+    context::span_reset(Subsystem::Storage, span!("a"));
+    {
+        let _b = context::span_enter(span!("b", id = 1));
+        {
+            let _c = context::span_enter(span!("c"));
+            assert_eq!(
+                context::span_chain().unwrap(),
+                "while a → while b (id=1) → while c"
+            );
+            assert_eq!(
+                context::get_dmsg().unwrap().as_ref(),
+                "while a → while b (id=1) → while c"
+            );
+        }
+        assert_eq!(context::span_chain().unwrap(), "while a → while b (id=1)");
+        let _d = context::span_enter(span!("d"));
+        assert_eq!(
+            context::span_chain().unwrap(),
+            "while a → while b (id=1) → while d"
+        );
+    }
+    assert_eq!(context::span_chain().unwrap(), "while a");
+    assert_eq!(context::get_dmsg().unwrap().as_ref(), "while a");
+    // a new operation discards the previous chain
+    context::span_reset(Subsystem::Init, span!("e"));
+    assert_eq!(context::span_chain().unwrap(), "while e");
+}
+
+#[test]
+fn span_error_inherits_chain() {
+    context::span_reset(Subsystem::Storage, span!("loading storage engine"));
+    let e = {
+        let _span = context::span_enter(span!("loading gns"));
+        Error::from(StorageError::RawJournalCorrupted)
+    };
+    assert!(e
+        .to_string()
+        .contains("while loading storage engine → while loading gns; "));
+    assert_eq!(
+        context::get_dmsg().unwrap().as_ref(),
+        "while loading storage engine"
+    );
+}
@@ -24,7 +24,13 @@
  *
 */
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use {
+    std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    tokio::sync::watch,
+};
 
 #[derive(Debug)]
 pub struct Status {
@@ -73,3 +79,34 @@ impl FractalToken {
         Self(())
     }
 }
+
+/// A token that can be used to cooperatively request a clean shutdown of the server (for example, by an embedder that
+/// doesn't want to send a signal)
+#[derive(Debug, Clone)]
+pub struct ShutdownToken {
+    cancelled: Arc<watch::Sender<bool>>,
+}
+
+impl ShutdownToken {
+    pub(super) fn new() -> Self {
+        Self {
+            cancelled: Arc::new(watch::channel(false).0),
+        }
+    }
+    /// Request a shutdown
+    #[allow(unused)]
+    pub fn cancel(&self) {
+        self.cancelled.send_replace(true);
+    }
+    /// Check if a shutdown was requested
+    #[allow(unused)]
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+    /// Wait until a shutdown is requested. Returns immediately if one already was
+    pub async fn cancelled(&self) {
+        let mut rx = self.cancelled.subscribe();
+        // the sender lives as long as we do, so this can't fail
+        let _ = rx.wait_for(|cancelled| *cancelled).await;
+    }
+}
@@ -26,7 +26,7 @@
 
 use {
     super::{
-        meta::{Config, DefConfig, NodeFlag, TreeElement},
+        meta::{Config, DefConfig, TreeElement},
         Node, RawTree,
     },
     crate::engine::{
@@ -271,7 +271,7 @@ where
                 _ if node.is_null() => {
                     self.stack.pop();
                 }
-                flag if super::hf(flag, NodeFlag::DATA) => {
+                _ if super::Node::is_leaf(node) => {
                     let data = unsafe {
                         // UNSAFE(@ohsayan): flagck
                         RawTree::<T, C>::read_data(current.sptr)
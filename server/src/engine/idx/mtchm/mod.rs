@@ -37,10 +37,10 @@ use crate::engine::sync::atm::ORD_ACQ;
 use {
     self::{
         iter::{IterKV, IterKey, IterVal},
-        meta::{CompressState, Config, DefConfig, LNode, NodeFlag, TreeElement},
+        meta::{CompressState, Config, Config2B, DefConfig, LNode, NodeFlag, TreeElement},
     },
     crate::engine::{
-        idx::meta::Comparable,
+        idx::meta::{AsHasher, Comparable},
         mem::UArray,
         sync::atm::{self, cpin, upin, Atomic, Guard, Owned, Shared, ORD_ACR, ORD_RLX},
     },
@@ -59,6 +59,7 @@ use {
 struct CHTMetricsData {
     split: AtomicUsize,
     hln: AtomicUsize,
+    compress: AtomicUsize,
 }
 
 pub struct CHTRuntimeLog {
@@ -78,6 +79,7 @@ impl CHTRuntimeLog {
         data: CHTMetricsData {
             split: Self::ZERO,
             hln: Self::ZERO,
+            compress: Self::ZERO,
         },
     };
     const fn new() -> Self {
@@ -94,12 +96,23 @@ impl CHTRuntimeLog {
         } else {
             ()
         }
+        fn hcompress(self: &Self) {
+            self.data.compress.fetch_add(1, ORD_ACQ);
+        } else {
+            ()
+        }
         #[cfg(test)]
         fn replnode(self: &Self) -> usize {
             self.data.hln.load(ORD_RLX)
         } else {
             0
         }
+        #[cfg(test)]
+        fn repcompress(self: &Self) -> usize {
+            self.data.compress.load(ORD_RLX)
+        } else {
+            0
+        }
     }
 }
 
@@ -157,6 +170,17 @@ impl<C: Config> Node<C> {
             branch: Self::NULL_BRANCH,
         }
     }
+    /// Returns true if this node holds data (an lnode or snode)
+    #[inline(always)]
+    fn is_leaf(n: &Shared<Self>) -> bool {
+        !n.is_null() && hf(ldfl(n), NodeFlag::DATA)
+    }
+    /// Returns true if this node is a branch
+    #[inline(always)]
+    #[cfg_attr(not(debug_assertions), allow(unused))]
+    fn is_branch(n: &Shared<Self>) -> bool {
+        !n.is_null() && !hf(ldfl(n), NodeFlag::DATA)
+    }
 }
 
 #[inline(always)]
@@ -235,6 +259,14 @@ impl<T, C: Config> RawTree<T, C> {
     }
 }
 
+impl<T, H: AsHasher> RawTree<T, Config2B<H>> {
+    /// Create a new tree that hashes keys using the given hasher state
+    #[allow(unused)]
+    pub fn with_hasher(h: H) -> Self {
+        Self::_new(h)
+    }
+}
+
 impl<T, C: Config> RawTree<T, C> {
     fn hash<Q>(&self, k: &Q) -> u64
     where
@@ -258,6 +290,19 @@ impl<T: TreeElement, C: Config> RawTree<T, C> {
     fn iter_val<'t, 'g, 'v>(&'t self, g: &'g Guard) -> IterVal<'t, 'g, 'v, T, C> {
         IterVal::new(self, g)
     }
+    /// Returns an owned snapshot of all the key-value pairs in the tree
+    ///
+    /// The buffer is sized using the current length, so unless there are concurrent inserts, this won't reallocate
+    #[allow(unused)]
+    pub fn to_owned_vec(&self, g: &Guard) -> Vec<(T::Key, T::Value)>
+    where
+        T::Key: Clone,
+        T::Value: Clone,
+    {
+        let mut ret = Vec::with_capacity(self.len());
+        ret.extend(self.iter_kv(g).map(|(k, v)| (k.clone(), v.clone())));
+        ret
+    }
 }
 
 impl<T: TreeElement, C: Config> RawTree<T, C> {
@@ -266,6 +311,75 @@ impl<T: TreeElement, C: Config> RawTree<T, C> {
             let _ = self.remove(k, g);
         });
     }
+    /// Apply `f` to the value for `k`, swapping in the modified entry. Returns false if the key wasn't found
+    ///
+    /// `f` works on a clone of the current value and might be called more than once if we race with another writer,
+    /// so it should have no side effects
+    #[allow(unused)]
+    pub fn for_each_mut<'g, F: Fn(&mut T::Value)>(&'g self, k: &T::Key, f: F, g: &'g Guard) -> bool
+    where
+        T: TreeElement<
+            IKey = <T as TreeElement>::Key,
+            IValue = <T as TreeElement>::Value,
+            VEx1 = (),
+            VEx2 = (),
+        >,
+        T::Key: Clone,
+        T::Value: Clone,
+    {
+        self.patch(patch::UpdateWith::new(k, f), g)
+    }
+    /// Replace every value in the tree with `f(key, old_value)`
+    ///
+    /// This is O(n) and each entry is swapped in individually, so under heavy write concurrency this can race with
+    /// (and repeatedly retry against) other writers. Avoid calling it on hot trees
+    #[allow(unused)]
+    pub fn update_all_values<F: Fn(&T::Key, T::Value) -> T::Value>(&self, f: &F, g: &Guard)
+    where
+        T: TreeElement<
+            IKey = <T as TreeElement>::Key,
+            IValue = <T as TreeElement>::Value,
+            VEx1 = (),
+            VEx2 = (),
+        >,
+        T::Key: Clone,
+        T::Value: Clone,
+    {
+        self.iter_key(g).for_each(|k| {
+            let _ = self.for_each_mut(k, |v| *v = f(k, v.clone()), g);
+        });
+    }
+    /// Insert either all of the given items or none of them. Returns false if any of the keys already exist (or
+    /// if a key is repeated in the batch)
+    ///
+    /// We first check that none of the keys exist and then insert them one by one. If a concurrent writer beats us
+    /// to one of the keys, the entries inserted so far are rolled back. Concurrent readers may observe some of the
+    /// entries before a rollback, and an entry that a concurrent writer has since updated is left as is
+    #[allow(unused)]
+    pub fn transactional_insert_batch<'g, I: IntoIterator<Item = T>>(
+        &'g self,
+        items: I,
+        g: &'g Guard,
+    ) -> bool
+    where
+        T: PartialEq,
+    {
+        let items: Vec<T> = items.into_iter().collect();
+        // phase 1: none of the keys should exist
+        if items.iter().any(|item| self.contains_key(item.key(), g)) {
+            return false;
+        }
+        // phase 2: insert, and roll back if we lost a race (or have a duplicate key)
+        for (i, item) in items.iter().enumerate() {
+            if !self.patch(patch::VanillaInsert(item.clone()), g) {
+                items[..i].iter().for_each(|item| {
+                    let _ = self._remove(patch::DeleteIfEq::new(item), g);
+                });
+                return false;
+            }
+        }
+        true
+    }
     fn patch<'g, P: patch::PatchWrite<T>>(&'g self, mut patch: P, g: &'g Guard) -> P::Ret<'g> {
         let hash = self.hash(patch.target());
         let mut level = C::LEVEL_ZERO;
@@ -284,7 +398,7 @@ impl<T: TreeElement, C: Config> RawTree<T, C> {
                     */
                     unsafe {
                         // UNSAFE(@ohsayan): we know that isn't the root and def doesn't have data (that's how the algorithm works)
-                        Self::compress(parent.unwrap(), child.unwrap(), g);
+                        self.compress(parent.unwrap(), child.unwrap(), g);
                     }
                     level = C::LEVEL_ZERO;
                     current = &self.root;
@@ -316,7 +430,7 @@ impl<T: TreeElement, C: Config> RawTree<T, C> {
                         }
                     }
                 }
-                flag if hf(flag, NodeFlag::DATA) => {
+                _ if Node::is_leaf(&node) => {
                     // so we have an lnode. well maybe an snode
                     let data = unsafe {
                         // UNSAFE(@ohsayan): flagck
@@ -446,6 +560,35 @@ impl<T: TreeElement, C: Config> RawTree<T, C> {
     ) -> Option<&'g T::Value> {
         self._lookup(access::RModeRef::new(k), g)
     }
+    /// Returns the value for `k`, calling `init` to create and insert one if the key is missing. If `init` fails,
+    /// nothing is inserted and the error is returned
+    #[allow(unused)]
+    pub fn try_get_or_insert_with<'g, E>(
+        &'g self,
+        k: T::Key,
+        init: impl FnOnce() -> Result<T::Value, E>,
+        g: &'g Guard,
+    ) -> Result<&'g T::Value, E>
+    where
+        T: TreeElement<
+            IKey = <T as TreeElement>::Key,
+            IValue = <T as TreeElement>::Value,
+            VEx1 = (),
+            VEx2 = (),
+        >,
+    {
+        if let Some(v) = self.get(&k, g) {
+            return Ok(v);
+        }
+        let e = T::new(k, init()?, (), ());
+        loop {
+            // if we lose the race to a concurrent insert, we'll simply return that value
+            let _ = self.patch(patch::VanillaInsert(e.clone()), g);
+            if let Some(v) = self.get(e.key(), g) {
+                return Ok(v);
+            }
+        }
+    }
     fn get_full<'g, Q: ?Sized + Comparable<T::Key>>(
         &'g self,
         k: &Q,
@@ -463,7 +606,7 @@ impl<T: TreeElement, C: Config> RawTree<T, C> {
                     // honestly, if this ran on the root I'm going to die laughing (@ohsayan)
                     return R::nx();
                 }
-                flag if hf(flag, NodeFlag::DATA) => {
+                _ if Node::is_leaf(&node) => {
                     let mut ret = R::nx();
                     return unsafe {
                         // UNSAFE(@ohsayan): checked flag + nullck
@@ -513,13 +656,13 @@ impl<T: TreeElement, C: Config> RawTree<T, C> {
                             compression as well. same thing here as the other routines....can we do anything to avoid
                             the expensive root traversal?
                         */
-                        Self::compress(p, c, g);
+                        self.compress(p, c, g);
                     }
                     levels.clear();
                     level = C::LEVEL_ZERO;
                     current = &self.root;
                 }
-                flag if hf(flag, NodeFlag::DATA) => {
+                _ if Node::is_leaf(&node) => {
                     let data = unsafe {
                         // UNSAFE(@ohsayan): flagck
                         Self::read_data(node)
@@ -532,7 +675,8 @@ impl<T: TreeElement, C: Config> RawTree<T, C> {
                     let r: LNode<T> = data
                         .iter()
                         .filter_map(|this_elem| {
-                            if patch.target().cmp_eq(this_elem.key()) {
+                            if patch.target().cmp_eq(this_elem.key()) && patch.ex_confirm(this_elem)
+                            {
                                 ret = P::ex(this_elem);
                                 rem = true;
                                 None
@@ -584,7 +728,7 @@ impl<T: TreeElement, C: Config> RawTree<T, C> {
                         }
                         if unsafe {
                             // UNSAFE(@ohsayan): we know for a fact that we only have sensible levels
-                            Self::compress(p, c, g)
+                            self.compress(p, c, g)
                         } == CompressState::RESTORED
                         {
                             // simply restored the earlier state, so let's stop
@@ -634,7 +778,7 @@ impl<T, C: Config> RawTree<T, C> {
         Self::new_lnode(d)
     }
     unsafe fn read_data<'g>(d: Shared<'g, Node<C>>) -> &'g LNode<T> {
-        debug_assert!(hf(ldfl(&d), NodeFlag::DATA));
+        debug_assert!(Node::is_leaf(&d));
         (d.as_raw() as *const LNode<_>)
             .as_ref()
             .expect("logic,nullptr in lnode")
@@ -642,13 +786,13 @@ impl<T, C: Config> RawTree<T, C> {
     /// SAFETY: Ensure you have some actual data and not random garbage
     #[inline(always)]
     unsafe fn ldrop(leaf: Shared<Node<C>>) {
-        debug_assert!(hf(ldfl(&leaf), NodeFlag::DATA));
+        debug_assert!(Node::is_leaf(&leaf));
         drop(Owned::<LNode<T>>::from_raw(leaf.as_raw() as *mut _))
     }
     unsafe fn _rdrop(node: Shared<Node<C>>) {
         match ldfl(&node) {
             _ if node.is_null() => {}
-            flag if hf(flag, NodeFlag::DATA) => Self::ldrop(node),
+            _ if Node::is_leaf(&node) => Self::ldrop(node),
             _ => {
                 // a branch
                 let this_branch = node.into_owned();
@@ -665,6 +809,7 @@ impl<T, C: Config> RawTree<T, C> {
         Self::_rdrop(node);
     }
     unsafe fn compress<'g>(
+        &self,
         parent: &Atomic<Node<C>>,
         child: Shared<'g, Node<C>>,
         g: &'g Guard,
@@ -673,7 +818,7 @@ impl<T, C: Config> RawTree<T, C> {
             We look at the child's children and determine whether we can clean the child up. Although the amount of
             memory we can save is not something very signficant but it becomes important with larger cardinalities
         */
-        debug_assert!(!hf(ldfl(&child), NodeFlag::DATA), "logic,compress lnode");
+        debug_assert!(Node::is_branch(&child), "logic,compress lnode");
         debug_assert_eq!(ldfl(&child), 0, "logic,compress pending delete node");
         let branch = child.deref();
         let mut continue_compress = true;
@@ -690,7 +835,7 @@ impl<T, C: Config> RawTree<T, C> {
                 // lol, dangling child
                 _ if this_child.is_null() => {}
                 // some data in here
-                flag if hf(flag, NodeFlag::DATA) => {
+                _ if Node::is_leaf(&this_child) => {
                     last_leaf = Some(this_child);
                     cnt += Self::read_data(this_child).len();
                 }
@@ -735,6 +880,10 @@ impl<T, C: Config> RawTree<T, C> {
                     // UNSAFE(@ohsayan): We're the thread in the last epoch who's seeing this; so, we're good
                     g.defer_destroy(child);
                 }
+                if ret != CompressState::RESTORED {
+                    // the branch is gone for good
+                    self.m.hcompress();
+                }
                 ret
             }
             Err(_) => {
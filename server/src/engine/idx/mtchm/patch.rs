@@ -168,6 +168,51 @@ impl<T: TreeElement> PatchWrite<T> for VanillaUpdateRet<T> {
     }
 }
 
+/// Update an existing value in place by applying `F` to a clone of it
+pub struct UpdateWith<'a, T: TreeElement, F> {
+    target: &'a T::Key,
+    f: F,
+}
+
+impl<'a, T: TreeElement, F> UpdateWith<'a, T, F> {
+    pub fn new(target: &'a T::Key, f: F) -> Self {
+        Self { target, f }
+    }
+}
+
+impl<'u, T, F> PatchWrite<T> for UpdateWith<'u, T, F>
+where
+    T: TreeElement<
+        IKey = <T as TreeElement>::Key,
+        IValue = <T as TreeElement>::Value,
+        VEx1 = (),
+        VEx2 = (),
+    >,
+    T::Key: Clone,
+    T::Value: Clone,
+    F: Fn(&mut T::Value),
+{
+    const WMODE: WriteFlag = WRITEMODE_REFRESH;
+    type Ret<'a> = bool;
+    type Target = T::Key;
+    fn target<'a>(&'a self) -> &Self::Target {
+        self.target
+    }
+    // nx
+    fn nx_ret<'a>() -> Self::Ret<'a> {
+        false
+    }
+    // ex
+    fn ex_apply(&mut self, current: &T) -> T {
+        let mut v = current.val().clone();
+        (self.f)(&mut v);
+        T::new(current.key().clone(), v, (), ())
+    }
+    fn ex_ret<'a>(_: &'a T) -> Self::Ret<'a> {
+        true
+    }
+}
+
 /*
     delete
 */
@@ -176,6 +221,11 @@ pub trait PatchDelete<T: TreeElement> {
     type Ret<'a>;
     type Target: Comparable<T::Key> + ?Sized + Hash;
     fn target(&self) -> &Self::Target;
+    /// returns false if the entry matching the target must be left as is
+    #[inline(always)]
+    fn ex_confirm(&self, _: &T) -> bool {
+        true
+    }
     fn ex<'a>(v: &'a T) -> Self::Ret<'a>;
     fn nx<'a>() -> Self::Ret<'a>;
 }
@@ -273,3 +323,34 @@ impl<'dr, T: TreeElement, U: Comparable<T::Key> + ?Sized> PatchDelete<T> for Del
         None
     }
 }
+
+/// Delete the entry with the same key as `target` only if the entry is still equal to `target`
+pub struct DeleteIfEq<'a, T: TreeElement> {
+    target: &'a T,
+}
+
+impl<'a, T: TreeElement> DeleteIfEq<'a, T> {
+    pub fn new(target: &'a T) -> Self {
+        Self { target }
+    }
+}
+
+impl<'d, T: TreeElement + PartialEq> PatchDelete<T> for DeleteIfEq<'d, T> {
+    type Ret<'a> = bool;
+    type Target = T::Key;
+    fn target(&self) -> &Self::Target {
+        self.target.key()
+    }
+    #[inline(always)]
+    fn ex_confirm(&self, current: &T) -> bool {
+        self.target == current
+    }
+    #[inline(always)]
+    fn ex<'a>(_: &'a T) -> Self::Ret<'a> {
+        true
+    }
+    #[inline(always)]
+    fn nx<'a>() -> Self::Ret<'a> {
+        false
+    }
+}
@@ -28,6 +28,7 @@ use {
     super::{
         imp::ChmCopy as _ChmCopy,
         meta::{Config, DefConfig},
+        patch,
     },
     crate::engine::{
         idx::{IndexBaseSpec, MTIndex},
@@ -35,7 +36,10 @@ use {
     },
     std::{
         hash::{BuildHasher, Hasher},
-        sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Barrier, RwLock, RwLockReadGuard, RwLockWriteGuard,
+        },
         thread::{self, JoinHandle},
         time::Duration,
     },
@@ -109,6 +113,144 @@ fn update_empty() {
     assert!(!idx.mt_update((10, 20), &cpin()));
 }
 
+#[test]
+fn for_each_mut() {
+    let idx = ChmU8::idx_init();
+    let g = cpin();
+    assert!(!idx.for_each_mut(&10, |v| *v += 1, &g));
+    assert!(idx.mt_insert((10, 20), &g));
+    assert!(idx.for_each_mut(&10, |v| *v += 1, &g));
+    assert_eq!(idx.mt_get(&10, &g).copied(), Some(21));
+    assert_eq!(idx.mt_len(), 1);
+}
+
+#[test]
+fn compress_count() {
+    let idx = Chm::<usize, usize>::idx_init();
+    let g = cpin();
+    (0..1024).for_each(|i| assert!(idx.mt_insert((i, i), &g)));
+    (0..1024).for_each(|i| assert!(idx.mt_delete(&i, &g)));
+    assert!(idx.idx_metrics().repcompress() > 0);
+}
+
+#[test]
+fn transactional_insert_batch() {
+    let idx = ChmU8::idx_init();
+    let g = cpin();
+    assert!(idx.transactional_insert_batch([(1, 1), (2, 2)], &g));
+    assert_eq!(idx.mt_len(), 2);
+    // an existing key fails the whole batch
+    assert!(!idx.transactional_insert_batch([(3, 3), (2, 2)], &g));
+    // so does a repeated key
+    assert!(!idx.transactional_insert_batch([(3, 3), (4, 4), (3, 3)], &g));
+    assert_eq!(idx.mt_len(), 2);
+    assert!(idx.mt_get(&3, &g).is_none());
+    assert!(idx.mt_get(&4, &g).is_none());
+}
+
+#[test]
+fn delete_if_eq() {
+    let idx = ChmU8::idx_init();
+    let g = cpin();
+    assert!(idx.mt_insert((1, 1), &g));
+    assert!(!idx._remove(patch::DeleteIfEq::new(&(1, 2)), &g));
+    assert_eq!(idx.mt_get(&1, &g), Some(&1));
+    assert!(idx._remove(patch::DeleteIfEq::new(&(1, 1)), &g));
+    assert!(idx.mt_get(&1, &g).is_none());
+    assert_eq!(idx.mt_len(), 0);
+}
+
+#[test]
+fn transactional_insert_batch_rollback_keeps_concurrent_update() {
+    for _ in 0..1000 {
+        let idx = Arc::new(ChmU8::idx_init());
+        let barrier = Arc::new(Barrier::new(3));
+        let batch_done = Arc::new(AtomicBool::new(false));
+        let batch = {
+            let (idx, barrier, batch_done) = (idx.clone(), barrier.clone(), batch_done.clone());
+            thread::spawn(move || {
+                barrier.wait();
+                let ret = idx.transactional_insert_batch([(1, 1), (2, 1)], &cpin());
+                batch_done.store(true, Ordering::Release);
+                ret
+            })
+        };
+        let conflicting = {
+            let (idx, barrier) = (idx.clone(), barrier.clone());
+            thread::spawn(move || {
+                barrier.wait();
+                idx.mt_insert((2, 2), &cpin())
+            })
+        };
+        let updater = {
+            let (idx, barrier, batch_done) = (idx.clone(), barrier.clone(), batch_done.clone());
+            thread::spawn(move || {
+                barrier.wait();
+                loop {
+                    let finished = batch_done.load(Ordering::Acquire);
+                    if idx.mt_update((1, 3), &cpin()) {
+                        return true;
+                    }
+                    if finished {
+                        return false;
+                    }
+                }
+            })
+        };
+        let batch_ok = batch.join().unwrap();
+        let conflict_ok = conflicting.join().unwrap();
+        let updated = updater.join().unwrap();
+        let g = cpin();
+        // exactly one of them gets the key
+        assert_ne!(batch_ok, conflict_ok);
+        if batch_ok {
+            assert!(updated);
+        }
+        if updated {
+            // the rollback must not remove the updated entry
+            assert_eq!(idx.mt_get(&1, &g), Some(&3));
+        } else {
+            assert!(idx.mt_get(&1, &g).is_none());
+        }
+    }
+}
+
+#[test]
+fn to_owned_vec() {
+    let idx = ChmU8::idx_init();
+    let g = cpin();
+    assert!(idx.to_owned_vec(&g).is_empty());
+    (0..10).for_each(|i| assert!(idx.mt_insert((i, i + 1), &g)));
+    let mut snap = idx.to_owned_vec(&g);
+    snap.sort();
+    assert_eq!(snap, (0..10).map(|i| (i, i + 1)).collect::<Vec<_>>());
+}
+
+#[test]
+fn try_get_or_insert_with() {
+    let idx = ChmU8::idx_init();
+    let g = cpin();
+    assert_eq!(idx.try_get_or_insert_with(1, || Err(()), &g), Err(()));
+    assert!(idx.mt_get(&1, &g).is_none());
+    assert_eq!(
+        idx.try_get_or_insert_with(1, || Ok::<_, ()>(10), &g),
+        Ok(&10)
+    );
+    // init isn't called if the key exists
+    assert_eq!(idx.try_get_or_insert_with(1, || Err(()), &g), Ok(&10));
+    assert_eq!(idx.mt_len(), 1);
+}
+
+#[test]
+fn update_all_values() {
+    let idx = ChmU8::idx_init();
+    let g = cpin();
+    (0..10).for_each(|i| assert!(idx.mt_insert((i, i), &g)));
+    idx.update_all_values(&|k, v| k + v, &g);
+    (0..10).for_each(|i| assert_eq!(idx.mt_get(&i, &g).copied(), Some(i * 2)));
+    assert_eq!(idx.mt_len(), 10);
+}
+
 const SPAM_QCOUNT: usize = if crate::util::IS_ON_CI {
     1_024
 } else if cfg!(miri) {
@@ -332,6 +474,20 @@ fn multispam_delete() {
     );
 }
 
+#[test]
+fn with_hasher() {
+    let idx = super::RawTree::<(u8, u8), super::meta::Config2B<LolState>>::with_hasher(LolState {
+        seed: 1,
+    });
+    let g = cpin();
+    assert!(idx.mt_insert((1, 1), &g));
+    assert!(idx.mt_insert((2, 2), &g));
+    // every key lands in the same lnode
+    assert_eq!(idx.idx_metrics().replnode(), 1);
+    assert_eq!(idx.mt_get(&1, &g).copied(), Some(1));
+    assert_eq!(idx.mt_get(&2, &g).copied(), Some(2));
+}
+
 #[test]
 fn multispam_lol() {
     let idx = Arc::new(super::RawTree::<StringTup, super::meta::Config2B<LolState>>::new());
@@ -317,3 +317,17 @@ mod uarray {
         assert!(a.is_empty());
     }
 }
+
+mod unsafe_apis {
+    use crate::engine::mem::unsafe_apis::{memcpy, memcpy_be};
+    #[test]
+    fn memcpy_be_roundtrip() {
+        let x = 0xDEAD_BEEF_CAFE_BABE_u64;
+        let be = x.to_be_bytes();
+        unsafe {
+            // UNSAFE(@ohsayan): the slices are exactly 8 bytes long
+            assert_eq!(u64::from_ne_bytes(memcpy_be(&be)), x);
+            assert_eq!(memcpy_be::<8>(&memcpy_be::<8>(&be)), memcpy::<8>(&be));
+        }
+    }
+}
@@ -88,3 +88,15 @@ pub unsafe fn memcpy<const N: usize>(src: &[u8]) -> [u8; N] {
     src.as_ptr().copy_to_nonoverlapping(dst.as_mut_ptr(), N);
     dst
 }
+
+/// Copy exactly `N` big-endian bytes from `src` to a new array of size `N` in native byte order (so that
+/// `from_ne_bytes` on the result is the same as `from_be_bytes` on `src`)
+#[inline(always)]
+#[allow(unused)]
+pub unsafe fn memcpy_be<const N: usize>(src: &[u8]) -> [u8; N] {
+    let mut dst = memcpy(src);
+    if cfg!(target_endian = "little") {
+        dst.reverse();
+    }
+    dst
+}
@@ -42,44 +42,85 @@ mod txn;
 #[cfg(test)]
 mod tests;
 // re-export
-pub use error::RuntimeResult;
+pub use {error::RuntimeResult, fractal::context::ContextFrame};
 
 use crate::engine::storage::SELoaded;
 
 use {
     self::{
         config::{ConfigEndpoint, ConfigEndpointTls, ConfigMode, Configuration},
-        fractal::context::{self, Subsystem},
+        fractal::{
+            context::{self, Subsystem},
+            error::ErrorContext,
+        },
     },
     crate::util::os::TerminationSignal,
-    tokio::sync::broadcast,
+    std::{sync::Arc, time::Duration},
+    tokio::sync::{broadcast, watch},
 };
 
-pub(super) fn set_context_init(msg: &'static str) {
-    context::set(Subsystem::Init, msg)
+/// Begin a new init operation, resetting the span chain with `frame` as its root (see [`span!`])
+pub(super) fn set_context_init(frame: ContextFrame) {
+    context::span_reset(Subsystem::Init, frame)
+}
+
+/// Enter a nested span in the current init operation, until the returned guard is dropped
+pub(super) fn enter_context_init(frame: ContextFrame) -> context::SpanGuard {
+    context::span_enter(frame)
 }
 
 /// Initialize all drivers, load all data
 ///
+/// `progress` is called with a short description at the start of each init phase. This is useful when embedding
+/// the server and stdout is captured (the binary just passes a no-op)
+///
 /// WARN: Must be in [`tokio::runtime::Runtime`] context!
 pub fn load_all(
     config: Configuration,
+    progress: impl Fn(&str),
 ) -> RuntimeResult<(Configuration, fractal::GlobalStateStart)> {
     // load configuration
     if config.mode == ConfigMode::Dev {
         warn!("running in dev mode");
     }
     info!("starting storage engine");
-    context::set_origin(Subsystem::Storage);
-    let SELoaded { gns } = storage::load(&config)?;
+    progress("loading journals");
+    context::span_reset(Subsystem::Storage, span!("loading storage engine"));
+    let SELoaded { gns } = storage::load(&config).with_location()?;
     info!("storage engine ready. initializing system");
+    progress("initializing system");
     let global = unsafe {
         // UNSAFE(@ohsayan): the only call we ever make
-        fractal::load_and_enable_all(gns)
+        fractal::load_and_enable_all(gns, config.to_yaml())
     };
     Ok((config, global))
 }
 
+/// Signals waiters once the server is ready to accept connections (see [`start`])
+#[derive(Debug, Clone)]
+pub struct ReadyNotifier {
+    ready: Arc<watch::Sender<bool>>,
+}
+
+impl ReadyNotifier {
+    pub fn new() -> Self {
+        Self {
+            ready: Arc::new(watch::channel(false).0),
+        }
+    }
+    /// Mark the server as ready
+    pub fn notify(&self) {
+        self.ready.send_replace(true);
+    }
+    #[cfg(test)]
+    /// Wait until the server is ready. Returns immediately if it already is
+    pub async fn ready(&self) {
+        let mut rx = self.ready.subscribe();
+        // the sender lives as long as we do, so this can't fail
+        let _ = rx.wait_for(|ready| *ready).await;
+    }
+}
+
 enum EndpointListeners {
     Insecure(net::Listener),
     Secure {
@@ -115,6 +156,7 @@ impl EndpointListeners {
 
 pub async fn start(
     termsig: TerminationSignal,
+    notifier: ReadyNotifier,
     Configuration {
         endpoints, system, ..
     }: Configuration,
@@ -171,11 +213,16 @@ pub async fn start(
         }
     };
     info!("{str}");
+    notifier.notify();
+    let shutdown = global.shutdown_token();
     tokio::select! {
         _ = endpoint_handles.listen() => {}
         _ = termsig => {
             info!("received terminate signal. waiting for inflight tasks to complete ...");
         }
+        _ = shutdown.cancelled() => {
+            info!("received shutdown request. waiting for inflight tasks to complete ...");
+        }
     }
     drop(signal);
     endpoint_handles.finish().await;
@@ -192,9 +239,34 @@ pub async fn start(
     Ok(())
 }
 
-pub fn finish(g: fractal::Global) {
-    unsafe {
-        // UNSAFE(@ohsayan): the only thing we do before exit
-        g.unload_all();
+/// Returned by [`finish_with_timeout`]
+pub enum FinishResult {
+    /// all components were closed cleanly
+    Clean,
+    /// some components failed to close (or panicked while closing), but every component was attempted
+    Failed(Vec<fractal::ComponentError>),
+    /// a component didn't close in time and was abandoned along with all remaining components. these may still be
+    /// writing to disk
+    Abandoned(Vec<fractal::ComponentError>),
+}
+
+impl FinishResult {
+    fn new(errors: Vec<fractal::ComponentError>) -> Self {
+        if errors.is_empty() {
+            Self::Clean
+        } else if errors.iter().any(fractal::ComponentError::is_timeout) {
+            Self::Abandoned(errors)
+        } else {
+            Self::Failed(errors)
+        }
     }
 }
+
+/// Close all storage components, waiting at most `timeout` for each one of them so that a hung disk doesn't block
+/// the process from exiting
+pub fn finish_with_timeout(g: fractal::Global, timeout: Duration) -> FinishResult {
+    FinishResult::new(unsafe {
+        // UNSAFE(@ohsayan): the only thing we do before exit
+        g.unload_all(timeout)
+    })
+}
@@ -111,6 +111,7 @@ impl<S: Socket> ConnectionHandler<S> {
         term_sig: broadcast::Receiver<()>,
         _inflight_complete: mpsc::Sender<()>,
     ) -> Self {
+        global.connection_opened();
         Self {
             socket: BufWriter::with_capacity(BUF_WRITE_CAP, socket),
             buffer: BytesMut::with_capacity(BUF_READ_CAP),
@@ -149,6 +150,12 @@ impl<S: Socket> ConnectionHandler<S> {
     }
 }
 
+impl<S> Drop for ConnectionHandler<S> {
+    fn drop(&mut self) {
+        self.global.connection_closed();
+    }
+}
+
 /// A TCP listener bound to a socket
 pub struct Listener {
     global: Global,
@@ -29,7 +29,7 @@ use crate::{
     util::compiler::{self, TaggedEnum},
 };
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, sky_macros::EnumMethods)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, sky_macros::EnumMethods, sky_macros::ValueDisplay)]
 #[repr(u8)]
 /// Low-level protocol errors
 pub enum ProtocolError {
@@ -184,6 +184,15 @@ fn parse_auth_with_state_updates() {
     assert_eq!(rounds, 3); // r1 = initial read, r2 = lengths, r3 = items
 }
 
+#[test]
+fn protocol_error_display() {
+    assert_eq!(
+        ProtocolError::CorruptedHSPacket.to_string(),
+        "ProtocolError(0)"
+    );
+    assert_eq!(ProtocolError::RejectAuth.to_string(), "ProtocolError(5)");
+}
+
 const HS_BAD_PACKET: [u8; 6] = *b"I\x00\0\0\0\0";
 const HS_BAD_VERSION_HS: [u8; 6] = *b"H\x01\0\0\0\0";
 const HS_BAD_VERSION_PROTO: [u8; 6] = *b"H\0\x01\0\0\0";
@@ -42,8 +42,12 @@ pub enum SysctlCommand<'a> {
     DropUser(UserDel<'a>),
     /// `systcl alter user ...`
     AlterUser(UserDecl<'a>),
-    /// `sysctl status`
+    /// `sysctl report status`
     ReportStatus,
+    /// `sysctl report config`
+    ReportConfig,
+    /// `sysctl report stats`
+    ReportStats,
 }
 
 impl<'a> SysctlCommand<'a> {
@@ -66,7 +70,9 @@ impl<'a> traits::ASTNode<'a> for SysctlCommand<'a> {
         let create = Token![create].eq(a) & b.ident_eq("user");
         let drop = Token![drop].eq(a) & b.ident_eq("user");
         let status = a.ident_eq("report") & b.ident_eq("status");
-        if !(create | drop | status | alter) {
+        let config = a.ident_eq("report") & b.ident_eq("config");
+        let stats = a.ident_eq("report") & b.ident_eq("stats");
+        if !(create | drop | status | alter | config | stats) {
             return Err(QueryError::QLUnknownStatement);
         }
         if create {
@@ -75,6 +81,10 @@ impl<'a> traits::ASTNode<'a> for SysctlCommand<'a> {
             UserDel::parse(state).map(SysctlCommand::DropUser)
         } else if alter {
             UserDecl::parse(state).map(SysctlCommand::AlterUser)
+        } else if config {
+            Ok(SysctlCommand::ReportConfig)
+        } else if stats {
+            Ok(SysctlCommand::ReportStats)
         } else {
             Ok(SysctlCommand::ReportStatus)
         }
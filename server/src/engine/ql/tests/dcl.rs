@@ -37,6 +37,20 @@ fn report_status_simple() {
     assert_eq!(q, SysctlCommand::ReportStatus)
 }
 
+#[test]
+fn report_config_simple() {
+    let query = lex_insecure(b"sysctl report config").unwrap();
+    let q = ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap();
+    assert_eq!(q, SysctlCommand::ReportConfig)
+}
+
+#[test]
+fn report_stats_simple() {
+    let query = lex_insecure(b"sysctl report stats").unwrap();
+    let q = ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap();
+    assert_eq!(q, SysctlCommand::ReportStats)
+}
+
 #[test]
 fn create_user_simple() {
     let query = lex_insecure(b"sysctl create user sayan with { password: 'mypass123' }").unwrap();
@@ -72,4 +72,10 @@ impl SCrc64 {
     pub fn finish(self) -> u64 {
         self.digest.finalize()
     }
+    /// Compute the checksum of `data` and check if it matches `expected`
+    pub fn verify_block(data: &[u8], expected: u64) -> bool {
+        let mut crc = Self::new();
+        crc.update(data);
+        crc.finish() == expected
+    }
 }
@@ -133,6 +133,40 @@ impl FileSystem {
         }
         std_fs::rename(from, to)
     }
+    /// Create a temporary file for `path` (at `path.tmp`) to be atomically moved into place using
+    /// [`Self::commit_temp`] once it has been completely written. A stale temporary file (left behind by an
+    /// interrupted commit) is discarded
+    pub fn create_temp(path: &str) -> IoResult<(File, String)> {
+        let tmp_path = format!("{path}.tmp");
+        match Self::remove_file(&tmp_path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+        File::create(&tmp_path).map(|f| (f, tmp_path))
+    }
+    /// Move a temporary file created using [`Self::create_temp`] into place, and sync the parent directory so that
+    /// the rename is durable
+    ///
+    /// NB: The caller must have already synced the temporary file
+    pub fn commit_temp(tmp_path: &str, final_path: &str) -> IoResult<()> {
+        Self::rename(tmp_path, final_path)?;
+        #[cfg(test)]
+        {
+            match Self::context() {
+                FSContext::Local => {}
+                FSContext::Virtual => return Ok(()),
+            }
+        }
+        #[cfg(unix)]
+        {
+            let parent = match std::path::Path::new(final_path).parent() {
+                Some(p) if !p.as_os_str().is_empty() => p,
+                _ => std::path::Path::new("."),
+            };
+            std_fs::File::open(parent)?.sync_all()?;
+        }
+        Ok(())
+    }
 }
 
 /*
@@ -563,3 +597,20 @@ impl BufferedWriter {
         }
     }
 }
+
+#[test]
+fn temp_file_commit() {
+    let (mut f, tmp_path) = FileSystem::create_temp("tempfile").unwrap();
+    assert_eq!(tmp_path, "tempfile.tmp");
+    f.fwrite_all(b"hello").unwrap();
+    f.fsync_all().unwrap();
+    drop(f);
+    // a stale temporary file must not get in the way
+    let (mut f, _) = FileSystem::create_temp("tempfile").unwrap();
+    f.fwrite_all(b"world").unwrap();
+    f.fsync_all().unwrap();
+    drop(f);
+    FileSystem::commit_temp(&tmp_path, "tempfile").unwrap();
+    assert_eq!(FileSystem::read("tempfile").unwrap(), b"world");
+    assert!(FileSystem::read(&tmp_path).is_err());
+}
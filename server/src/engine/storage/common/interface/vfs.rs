@@ -70,17 +70,6 @@ pub(super) struct VFile {
     pos: usize,
 }
 
-#[derive(Debug, PartialEq)]
-/// Result of opening a file
-/// - Created: newly created file
-/// - Existing: existing file that was reopened
-pub enum FileOpen<CF, EF = CF> {
-    /// new file
-    Created(CF),
-    /// existing file
-    Existing(EF),
-}
-
 #[derive(Debug)]
 pub struct VFileDescriptor(pub(super) Box<str>);
 
@@ -227,17 +216,23 @@ impl VirtualFS {
     pub fn fs_rename(&mut self, from: &str, to: &str) -> IoResult<()> {
         // get file data
         let data = self.with_file(from, |f| Ok(f.data.clone()))?;
-        // create new file
-        let file = self.fs_fopen_or_create_rw(to)?;
-        match file {
-            FileOpen::Created(c) => self.with_file_mut(&c.0, |f| Ok(f.data = data))?,
-            FileOpen::Existing(c) => self.with_file_mut(&c.0, |f| {
-                f.data = data;
-                f.pos = 0;
-                f.read = false;
-                f.write = false;
-                Ok(())
-            })?,
+        // create new file (without opening a descriptor, since closing it needs the lock we already hold)
+        let (target_file, components) = util::split_target_and_components(to);
+        let target_dir = util::find_target_dir_mut(components, &mut self.root)?;
+        match target_dir.entry(target_file.into()) {
+            Entry::Occupied(oe) => match oe.get() {
+                VNode::File(f) => {
+                    let mut f = f.write();
+                    f.data = data;
+                    f.pos = 0;
+                    f.read = false;
+                    f.write = false;
+                }
+                VNode::Dir(_) => return err::item_is_not_file(),
+            },
+            Entry::Vacant(v) => {
+                v.insert(VNode::File(RwLock::new(VFile::new(false, false, data, 0))));
+            }
         }
         // delete old file
         self.fs_remove_file(from)
@@ -313,26 +308,6 @@ impl VirtualFS {
             root: HashMap::new(),
         }
     }
-    fn fs_fopen_or_create_rw(&mut self, fpath: &str) -> IoResult<FileOpen<VFileDescriptor>> {
-        // components
-        let (target_file, components) = util::split_target_and_components(fpath);
-        let target_dir = util::find_target_dir_mut(components, &mut self.root)?;
-        match target_dir.entry(target_file.into()) {
-            Entry::Occupied(oe) => match oe.get() {
-                VNode::File(f) => {
-                    let mut f = f.write();
-                    f.read = true;
-                    f.write = true;
-                    Ok(FileOpen::Existing(VFileDescriptor(fpath.into())))
-                }
-                VNode::Dir(_) => return err::item_is_not_file(),
-            },
-            Entry::Vacant(v) => {
-                v.insert(VNode::File(RwLock::new(VFile::new(true, true, vec![], 0))));
-                Ok(FileOpen::Created(VFileDescriptor(fpath.into())))
-            }
-        }
-    }
     pub(super) fn with_file_mut<T>(
         &self,
         fpath: &str,
@@ -464,3 +439,35 @@ mod util {
         Ok(current)
     }
 }
+
+#[test]
+fn rename_file() {
+    fn create(path: &str, data: &[u8]) {
+        let fd = VirtualFS::instance().write().fs_fcreate_rw(path).unwrap();
+        VirtualFS::instance()
+            .write()
+            .with_file_mut(&fd.0, |f| f.fwrite(data).map(|_| ()))
+            .unwrap();
+    }
+    let read = |path| VirtualFS::instance().read().get_data(path);
+    // onto a new file
+    create("vfs_rename_a", b"hello");
+    VirtualFS::instance()
+        .write()
+        .fs_rename("vfs_rename_a", "vfs_rename_b")
+        .unwrap();
+    assert_eq!(read("vfs_rename_b").unwrap(), b"hello");
+    assert!(read("vfs_rename_a").is_err());
+    // onto an existing file
+    create("vfs_rename_a", b"world");
+    VirtualFS::instance()
+        .write()
+        .fs_rename("vfs_rename_a", "vfs_rename_b")
+        .unwrap();
+    assert_eq!(read("vfs_rename_b").unwrap(), b"world");
+    assert!(read("vfs_rename_a").is_err());
+    VirtualFS::instance()
+        .write()
+        .fs_remove_file("vfs_rename_b")
+        .unwrap();
+}
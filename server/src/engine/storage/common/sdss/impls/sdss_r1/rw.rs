@@ -246,6 +246,18 @@ impl<S: FileSpecV1> TrackedReader<S> {
         self.tracked_read(&mut buf)?;
         Ok(buf)
     }
+    /// Read a block of the given size without consuming it. Neither the cursor nor the checksum is affected
+    #[allow(unused)]
+    pub fn peek_block<const N: usize>(&mut self) -> IoResult<[u8; N]> {
+        if !self.has_left(N as _) {
+            return Err(SysIOError::from(std::io::ErrorKind::InvalidInput).into_inner());
+        }
+        let mut buf = [0; N];
+        self.f.read_buffer(&mut buf)?;
+        // seeking discards the read buffer, so we're back exactly where we started
+        self.f.seek_from_start(self.cursor)?;
+        Ok(buf)
+    }
     /// Tracked read of a [`u64`] value
     pub fn read_u64_le(&mut self) -> IoResult<u64> {
         Ok(u64::from_le_bytes(self.read_block()?))
@@ -379,6 +391,10 @@ impl<
     pub fn is_dirty(&self) -> bool {
         !self.buf.is_empty()
     }
+    /// Returns the number of bytes sitting in the buffer that haven't been written to the file yet
+    pub fn unflushed_bytes(&self) -> usize {
+        self.buf.len()
+    }
 }
 
 impl<
@@ -575,6 +591,7 @@ fn check_vfs_buffering() {
             twriter.tracked_write(&[0; 8192])?;
             assert_eq!(rawfile(), compiled_header);
             assert_eq!(twriter.cursor_usize(), Header::SIZE);
+            assert_eq!(twriter.unflushed_bytes(), 8192);
         }
         {
             // W1: write one more byte; buf should be flushed
@@ -589,6 +606,7 @@ fn check_vfs_buffering() {
             twriter.flush_buf()?;
             let _raw_file = rawfile();
             assert_eq!(twriter.cursor_usize(), Header::SIZE + 8192 + 1);
+            assert_eq!(twriter.unflushed_bytes(), 0);
             assert_eq!(&_raw_file[..Header::SIZE], compiled_header);
             assert_eq!(&_raw_file[Header::SIZE..], vec![0u8; 8193]);
         }
@@ -623,3 +641,31 @@ fn check_vfs_buffering() {
     }
     .unwrap()
 }
+
+#[test]
+fn check_peek_block() {
+    use crate::engine::storage::v2::raw::spec::SystemDatabaseV1;
+    closure! {
+        let mut twriter: TrackedWriter<SystemDatabaseV1> =
+            TrackedWriter::new(SdssFile::create("peekfile")?)?;
+        twriter.tracked_write(&[1, 2, 3, 4, 5, 6, 7, 8])?;
+        twriter.flush_buf()?;
+        drop(twriter);
+        let mut treader = TrackedReader::<SystemDatabaseV1>::new(SdssFile::open("peekfile")?)?;
+        let start = treader.cursor();
+        // peeking must neither move the cursor nor update the checksum
+        assert_eq!(treader.peek_block::<4>()?, [1, 2, 3, 4]);
+        assert_eq!(treader.peek_block::<4>()?, [1, 2, 3, 4]);
+        assert_eq!(treader.cursor(), start);
+        assert_eq!(treader.current_checksum(), SCrc64::new().finish());
+        // a tracked read should still see the peeked bytes
+        assert_eq!(treader.read_block::<2>()?, [1, 2]);
+        assert_eq!(treader.peek_block::<6>()?, [3, 4, 5, 6, 7, 8]);
+        assert_eq!(treader.read_block::<6>()?, [3, 4, 5, 6, 7, 8]);
+        assert!(treader.is_eof());
+        // can't peek past EOF
+        assert!(treader.peek_block::<1>().is_err());
+        RuntimeResult::Ok(())
+    }
+    .unwrap()
+}
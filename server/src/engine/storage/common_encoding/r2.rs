@@ -38,7 +38,7 @@ use {
             txn::gns::sysctl::{AlterUserTxn, CreateUserTxn, DropUserTxn},
             RuntimeResult,
         },
-        util::EndianQW,
+        util::{codec::SkyEncodable, EndianQW},
     },
 };
 
@@ -193,13 +193,16 @@ impl<'a> PersistObject for AlterUserTxn<'a> {
     drop user txn
 */
 
-pub struct DropUserPayload(Box<str>);
+#[derive(Debug, PartialEq, sky_macros::SkyCodec)]
+pub struct DropUserPayload {
+    username: String,
+}
 
 impl<'a> GNSEvent for DropUserTxn<'a> {
     type CommitType = Self;
     type RestoreType = DropUserPayload;
     fn update_global_state(
-        DropUserPayload(username): Self::RestoreType,
+        DropUserPayload { username }: Self::RestoreType,
         gns: &GNSData,
     ) -> RuntimeResult<()> {
         if gns.sys_db().__raw_delete_user(&username) {
@@ -211,27 +214,49 @@ impl<'a> GNSEvent for DropUserTxn<'a> {
 }
 
 impl<'a> PersistObject for DropUserTxn<'a> {
-    const METADATA_SIZE: usize = sizeof!(u64);
+    // the payload carries its own length prefix, so there is no separate metadata
+    const METADATA_SIZE: usize = 0;
     type InputType = Self;
     type OutputType = DropUserPayload;
-    type Metadata = u64;
-    fn pretest_can_dec_object(scanner: &BufferedScanner, md: &Self::Metadata) -> bool {
-        scanner.has_left(*md as usize)
-    }
-    fn meta_enc(buf: &mut Vec<u8>, data: Self::InputType) {
-        buf.extend(data.username().len().u64_bytes_le())
+    type Metadata = ();
+    fn pretest_can_dec_object(_: &BufferedScanner, _: &Self::Metadata) -> bool {
+        // bounds are checked while decoding
+        true
     }
-    unsafe fn meta_dec(scanner: &mut BufferedScanner) -> RuntimeResult<Self::Metadata> {
-        Ok(scanner.next_u64_le())
+    fn meta_enc(_: &mut Vec<u8>, _: Self::InputType) {}
+    unsafe fn meta_dec(_: &mut BufferedScanner) -> RuntimeResult<Self::Metadata> {
+        Ok(())
     }
     fn obj_enc(buf: &mut Vec<u8>, data: Self::InputType) {
-        buf.extend(data.username().as_bytes());
+        // [username length: 8B][username]
+        DropUserPayload {
+            username: data.username().to_owned(),
+        }
+        .encode_to(buf)
     }
     unsafe fn obj_dec(
         s: &mut BufferedScanner,
-        md: Self::Metadata,
+        _: Self::Metadata,
     ) -> RuntimeResult<Self::OutputType> {
-        let username = dec::utils::decode_string(s, md as usize)?;
-        Ok(DropUserPayload(username.into_boxed_str()))
+        let (payload, rem) = DropUserPayload::decode_from(s.current_buffer())
+            .ok_or(StorageError::InternalDecodeStructureCorruptedPayload)?;
+        // UNSAFE(@ohsayan): decode_from only consumes bytes from the current buffer
+        s.incr_cursor_by(s.remaining() - rem.len());
+        Ok(payload)
     }
 }
+
+#[test]
+fn drop_user_txn_encoding() {
+    let encoded = super::r1::enc::full_self(DropUserTxn::new("sayan"));
+    // [username length: 8B][username]
+    assert_eq!(encoded, [&5u64.to_le_bytes()[..], b"sayan"].concat());
+    assert_eq!(
+        super::r1::dec::full::<DropUserTxn>(&encoded).unwrap(),
+        DropUserPayload {
+            username: "sayan".into()
+        }
+    );
+    // truncated payloads don't decode
+    assert!(super::r1::dec::full::<DropUserTxn>(&encoded[..encoded.len() - 1]).is_err());
+}
@@ -63,20 +63,22 @@ pub fn load(cfg: &Configuration) -> RuntimeResult<SELoaded> {
         warn!("older storage format detected");
         // this is an old install
         info!("loading data");
-        context::set_dmsg("loading storage-v1 in compatibility mode");
-        let gns = v1::load_gns_prepare_migration()?;
+        let gns = {
+            let _span = context::span_enter(span!("loading storage-v1 in compatibility mode"));
+            v1::load_gns_prepare_migration()?
+        };
         info!("loaded data. now upgrading to new storage format");
-        context::set_dmsg("upgrading storage-v1 to storage-v2 format");
+        let _span = context::span_enter(span!("upgrading storage-v1 to storage-v2 format"));
         return v2::recreate(gns);
     }
     if !Path::new(v2::GNS_PATH).is_file() {
         info!("initializing databases");
-        context::set_dmsg("creating databases");
+        let _span = context::span_enter(span!("creating databases"));
         // this is a new install
         v2::initialize_new(cfg)
     } else {
         info!("reinitializing databases");
-        context::set_dmsg("loading databases");
+        let _span = context::span_enter(span!("loading databases"));
         v2::restore(cfg)
     }
 }
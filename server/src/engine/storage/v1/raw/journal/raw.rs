@@ -42,7 +42,10 @@
 */
 
 use {
-    super::super::{rw::SDSSFileIO, spec::Header},
+    super::super::{
+        rw::{AppendOnlyFile, SDSSFileIO},
+        spec::Header,
+    },
     crate::{
         engine::{
             error::{RuntimeResult, StorageError},
@@ -56,15 +59,53 @@ use {
     std::marker::PhantomData,
 };
 
-const CRC: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+const CRC_ISO_HDLC: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+const CRC_ISCSI: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISCSI);
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// The CRC32 polynomial used to checksum event payloads
+pub enum CrcAlgorithm {
+    /// `CRC_32_ISO_HDLC`
+    IsoHdlc,
+    /// `CRC_32_ISCSI` (Castagnoli)
+    #[allow(unused)]
+    Iscsi,
+}
+
+impl CrcAlgorithm {
+    const fn crc(self) -> &'static crc::Crc<u32> {
+        match self {
+            Self::IsoHdlc => &CRC_ISO_HDLC,
+            Self::Iscsi => &CRC_ISCSI,
+        }
+    }
+    pub fn checksum(self, data: &[u8]) -> u32 {
+        self.crc().checksum(data)
+    }
+}
 
 pub fn load_journal<TA: JournalAdapter, F: sdss::sdss_r1::FileSpecV1<DecodeArgs = ()>>(
     log_file_name: &str,
     gs: &TA::GlobalState,
 ) -> RuntimeResult<JournalWriter<TA>> {
     let (file, _) = SDSSFileIO::open::<F>(log_file_name)?;
-    let (file, last_txn_id) = JournalReader::<TA>::scroll(file, gs)?;
-    JournalWriter::new(file, last_txn_id, false)
+    let (file, scroll) = JournalReader::<TA>::scroll(file, gs)?;
+    if scroll.recovered_events > 0 {
+        warn!(
+            "journal `{log_file_name}` was recovered ({} event(s) were reverted)",
+            scroll.recovered_events
+        );
+    }
+    JournalWriter::new(file, scroll.last_txn_id, false)
+}
+
+/// Returned by [`JournalReader::scroll`]
+#[derive(Debug, PartialEq)]
+pub struct JournalScrollResult {
+    /// the ID of the last event in the journal
+    pub last_txn_id: u64,
+    /// the number of events that were reverted by the recovery plugin
+    pub recovered_events: usize,
 }
 
 /// The journal adapter
@@ -73,6 +114,8 @@ pub trait JournalAdapter {
     const DENY_NONAPPEND: bool = true;
     /// enable/disable automated recovery algorithms
     const RECOVERY_PLUGIN: bool;
+    /// the CRC32 polynomial used to checksum event payloads
+    const CRC_POLYNOMIAL: CrcAlgorithm = CrcAlgorithm::IsoHdlc;
     /// The journal event
     type JournalEvent;
     /// The global state, which we want to modify on decoding the event
@@ -178,6 +221,7 @@ pub struct JournalReader<TA> {
     evid: u64,
     closed: bool,
     remaining_bytes: u64,
+    recovered_events: usize,
     _m: PhantomData<TA>,
 }
 
@@ -189,6 +233,7 @@ impl<TA: JournalAdapter> JournalReader<TA> {
             evid: 0,
             closed: false,
             remaining_bytes: log_size,
+            recovered_events: 0,
             _m: PhantomData,
         })
     }
@@ -239,7 +284,7 @@ impl<TA: JournalAdapter> JournalReader<TA> {
         }
         let mut payload = vec![0; entry_metadata.event_payload_len as usize];
         self.logfile_read_into_buffer(&mut payload)?; // exit jump -> we checked if enough data is there, but the read failed so this is not our business
-        if compiler::unlikely(CRC.checksum(&payload) != entry_metadata.event_crc) {
+        if compiler::unlikely(TA::CRC_POLYNOMIAL.checksum(&payload) != entry_metadata.event_crc) {
             return compiler::cold_call(|| self.try_recover_journal_strategy_simple_reverse());
         }
         if compiler::unlikely(TA::decode_and_update_state(&payload, gs).is_err()) {
@@ -288,22 +333,30 @@ impl<TA: JournalAdapter> JournalReader<TA> {
             & (entry.event_source_md == EventSourceMarker::RECOVERY_REVERSE_LAST_JOURNAL);
         self._incr_evid();
         if okay {
+            self.recovered_events += 1;
             return Ok(());
         } else {
             Err(StorageError::JournalCorrupted.into())
         }
     }
-    /// Read and apply all events in the given log file to the global state, returning the (open file, last event ID)
+    /// Read and apply all events in the given log file to the global state, returning the open file and a
+    /// [`JournalScrollResult`]
     pub fn scroll(
         file: SDSSFileIO<File>,
         gs: &TA::GlobalState,
-    ) -> RuntimeResult<(SDSSFileIO<File>, u64)> {
+    ) -> RuntimeResult<(SDSSFileIO<File>, JournalScrollResult)> {
         let mut slf = Self::new(file)?;
         while !slf.end_of_file() {
             slf.rapply_next_event(gs)?;
         }
         if slf.closed {
-            Ok((slf.log_file.downgrade_reader(), slf.evid))
+            Ok((
+                slf.log_file.downgrade_reader(),
+                JournalScrollResult {
+                    last_txn_id: slf.evid,
+                    recovered_events: slf.recovered_events,
+                },
+            ))
         } else {
             Err(StorageError::JournalCorrupted.into())
         }
@@ -338,8 +391,8 @@ impl<TA> JournalReader<TA> {
 }
 
 pub struct JournalWriter<TA> {
-    /// the txn log file
-    log_file: SDSSFileIO<File>,
+    /// the txn log file (append only, if the adapter denies non-append writes)
+    log_file: AppendOnlyFile<File>,
     /// the id of the **next** journal
     id: u64,
     _m: PhantomData<TA>,
@@ -347,7 +400,8 @@ pub struct JournalWriter<TA> {
 }
 
 impl<TA: JournalAdapter> JournalWriter<TA> {
-    pub fn new(mut log_file: SDSSFileIO<File>, last_txn_id: u64, new: bool) -> RuntimeResult<Self> {
+    pub fn new(log_file: SDSSFileIO<File>, last_txn_id: u64, new: bool) -> RuntimeResult<Self> {
+        let mut log_file = AppendOnlyFile::new(log_file, TA::DENY_NONAPPEND);
         let log_size = log_file.file_length()?;
         log_file.seek_from_start(log_size)?; // avoid jumbling with headers
         let mut slf = Self {
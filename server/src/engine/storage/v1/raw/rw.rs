@@ -204,3 +204,55 @@ impl<F: FileWrite + FileWriteExt> SDSSFileIO<F> {
         self.f.fsync_all()
     }
 }
+
+/// An [`SDSSFileIO`] that only permits appends (if enabled). Repositioning the cursor anywhere but the end of the file
+/// (which would let us overwrite existing data) will panic in debug builds and error in release builds
+#[derive(Debug)]
+pub struct AppendOnlyFile<F> {
+    f: SDSSFileIO<F>,
+    deny_nonappend: bool,
+}
+
+impl<F> AppendOnlyFile<F> {
+    pub fn new(f: SDSSFileIO<F>, deny_nonappend: bool) -> Self {
+        Self { f, deny_nonappend }
+    }
+}
+
+impl<F: FileExt> AppendOnlyFile<F> {
+    pub fn file_length(&self) -> IoResult<u64> {
+        self.f.file_length()
+    }
+    pub fn seek_from_start(&mut self, by: u64) -> IoResult<()> {
+        if self.deny_nonappend {
+            let len = self.f.file_length()?;
+            if by != len {
+                if cfg!(debug_assertions) {
+                    panic!("non-append seek to {by} on append-only file of length {len}");
+                }
+                return Err(std::io::Error::other("non-append seek on append-only file"));
+            }
+        }
+        self.f.seek_from_start(by)
+    }
+}
+
+impl<F: FileWrite + FileWriteExt> AppendOnlyFile<F> {
+    pub fn fsynced_write(&mut self, data: &[u8]) -> IoResult<()> {
+        self.f.fsynced_write(data)
+    }
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "non-append seek"))]
+fn append_only_file_denies_nonappend_seek() {
+    use crate::engine::storage::common::interface::fs::FileSystem;
+    let mut f = File::create("append_only_file_denies_nonappend_seek.db").unwrap();
+    f.fwrite_all(b"hello").unwrap();
+    let mut f = AppendOnlyFile::new(SDSSFileIO::new(f), true);
+    // seeking to the end is fine
+    f.seek_from_start(5).unwrap();
+    let e = f.seek_from_start(0);
+    FileSystem::remove_file("append_only_file_denies_nonappend_seek.db").unwrap();
+    assert!(e.is_err());
+}
@@ -55,17 +55,23 @@ pub const GNS_PATH: &str = v1::GNS_PATH;
 pub const DATA_DIR: &str = v1::DATA_DIR;
 
 pub fn recreate(gns: GNSData) -> RuntimeResult<SELoaded> {
-    context::set_dmsg("creating gns");
-    let mut gns_driver = impls::gns_log::GNSDriver::create_gns()?;
+    let mut gns_driver = {
+        let _span = context::span_enter(span!("creating gns"));
+        impls::gns_log::GNSDriver::create_gns()?
+    };
     // create all spaces
-    context::set_dmsg("creating all spaces");
     for (space_name, space) in gns.idx().read().iter() {
+        let _span = context::span_enter(span!("creating space", name = space_name));
         FileSystem::create_dir_all(&paths_v1::space_dir(space_name, space.get_uuid()))?;
         gns_driver.commit_event(CreateSpaceTxn::new(space.props(), &space_name, space))?;
     }
     // create all models
-    context::set_dmsg("creating all models");
     for (model_id, model) in gns.idx_models().read().iter() {
+        let _span = context::span_enter(span!(
+            "creating model",
+            space = model_id.space(),
+            model = model_id.entity()
+        ));
         let model_data = model.data();
         let space_uuid = gns.idx().read().get(model_id.space()).unwrap().get_uuid();
         FileSystem::create_dir_all(&paths_v1::model_dir(
@@ -89,8 +95,8 @@ pub fn recreate(gns: GNSData) -> RuntimeResult<SELoaded> {
         model.driver().initialize_model_driver(model_driver);
     }
     // create all users
-    context::set_dmsg("creating all users");
     for (user_name, user) in gns.sys_db().users().read().iter() {
+        let _span = context::span_enter(span!("creating user", name = user_name));
         gns_driver.commit_event(CreateUserTxn::new(&user_name, user.hash()))?;
     }
     Ok(SELoaded {
@@ -119,14 +125,16 @@ pub fn initialize_new(config: &Configuration) -> RuntimeResult<SELoaded> {
 
 pub fn restore(cfg: &Configuration) -> RuntimeResult<SELoaded> {
     let gns = GNSData::empty();
-    context::set_dmsg("loading gns");
-    let mut gns_driver = impls::gns_log::GNSDriver::open_gns(&gns)?;
+    let mut gns_driver = {
+        let _span = context::span_enter(span!("loading gns"));
+        impls::gns_log::GNSDriver::open_gns(&gns)?
+    };
     for (id, model) in gns.idx_models().write().iter_mut() {
         let model_data = model.data();
         let space_uuid = gns.idx().read().get(id.space()).unwrap().get_uuid();
         let model_data_file_path =
             paths_v1::model_path(id.space(), space_uuid, id.entity(), model_data.get_uuid());
-        context::set_dmsg(format!("loading model driver in {model_data_file_path}"));
+        let _span = context::span_enter(span!("loading model driver", path = model_data_file_path));
         let model_driver =
             impls::mdl_journal::ModelDriver::open_model_driver(model_data, &model_data_file_path)?;
         model.driver().initialize_model_driver(model_driver);
@@ -143,7 +151,9 @@ pub fn restore(cfg: &Configuration) -> RuntimeResult<SELoaded> {
     {
         // the password was changed
         warn!("root password changed via configuration");
-        context::set_dmsg("updating password to system database from configuration");
+        let _span = context::span_enter(span!(
+            "updating password to system database from configuration"
+        ));
         let phash = rcrypt::hash(&cfg.auth.root_key, rcrypt::DEFAULT_COST).unwrap();
         gns_driver.commit_event(AlterUserTxn::new(SystemDatabase::ROOT_ACCOUNT, &phash))?;
         gns.sys_db()
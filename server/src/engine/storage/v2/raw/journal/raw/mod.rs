@@ -76,6 +76,38 @@ where
     RawJournalWriter::new(initializer, file)
 }
 
+/// The result of verifying a journal (see [`verify_journal`])
+#[derive(Debug, PartialEq)]
+#[allow(unused)]
+pub struct JournalVerifyResult {
+    /// the number of events (server and driver) that were read successfully
+    pub valid_events: u64,
+    /// the number of corrupted events
+    pub corrupt_events: u64,
+    /// the number of bytes past the last valid event
+    pub trailing_bytes: u64,
+    /// true if the journal was closed properly and has no corrupted events or trailing bytes
+    pub is_clean: bool,
+}
+
+/// Verify the integrity of a journal without opening it for writing
+///
+/// Since every event depends on the ones before it, the scan can't continue past a corrupted event. Hence
+/// `corrupt_events` is at most 1 and everything after the last valid event is counted as trailing bytes
+///
+/// NB: server events are validated by applying them to `gs`, so pass a scratch instance and not the live state
+#[allow(unused)]
+pub fn verify_journal<J: RawJournalAdapter>(
+    log_path: &str,
+    gs: &J::GlobalState,
+) -> RuntimeResult<JournalVerifyResult>
+where
+    J::Spec: FileSpecV1<DecodeArgs = ()>,
+{
+    let log = SdssFile::<J::Spec>::open(log_path)?;
+    RawJournalReader::<J>::verify(log, gs)
+}
+
 #[derive(Debug)]
 pub struct JournalInitializer {
     cursor: u64,
@@ -427,12 +459,11 @@ impl DriverEvent {
             last_offset = cpblk!(Self::OFFSET_5_LAST_OFFSET);
             last_txn_id = cpblk!(Self::OFFSET_6_LAST_TXN_ID);
             // now validate checksum
-            let mut checksum_ = SCrc64::new();
-            checksum_
-                .update(&block[Self::OFFSET_3_PAYLOAD_LEN.start..Self::OFFSET_6_LAST_TXN_ID.end]);
-            let target_checksum = checksum_.finish();
             let invalid_ev_dscr = driver_event_ > DriverEventKind::MAX_DSCR as u64;
-            let invalid_ck = checksum != target_checksum;
+            let invalid_ck = !SCrc64::verify_block(
+                &block[Self::OFFSET_3_PAYLOAD_LEN.start..Self::OFFSET_6_LAST_TXN_ID.end],
+                checksum,
+            );
             let invalid_pl_size = payload_len != 3;
             if invalid_ev_dscr | invalid_ck | invalid_pl_size {
                 return None;
@@ -450,7 +481,15 @@ impl DriverEvent {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy, sky_macros::EnumMethods, sky_macros::TaggedEnum)]
+#[derive(
+    Debug,
+    PartialEq,
+    Clone,
+    Copy,
+    sky_macros::EnumMethods,
+    sky_macros::TaggedEnum,
+    sky_macros::DisplayEnum,
+)]
 #[repr(u8)]
 pub(super) enum DriverEventKind {
     Reopened = 0,
@@ -493,6 +532,9 @@ where
 }
 
 const SERVER_EV_MASK: u64 = 1 << (u64::BITS - 1);
+/// if more than these many bytes are left unflushed after an event is committed, the buffer is flushed early (this
+/// only applies to journals that don't sync on every commit)
+const MAX_BUFFER_FILL: usize = 4096;
 
 impl<J: RawJournalAdapter> RawJournalWriter<J> {
     /// Initialize a new [`RawJournalWriter`] using a [`JournalInitializer`]
@@ -552,6 +594,9 @@ impl<J: RawJournalAdapter> RawJournalWriter<J> {
                 // should fsync after event
                 log_file.flush_sync()?;
                 jtrace_writer!(CommitCommitServerEventSyncCompleted);
+            } else if log_file.unflushed_bytes() > MAX_BUFFER_FILL {
+                // batch up events, but don't let the buffer grow until it's forcibly flushed mid-event
+                log_file.flush_buf()?;
             }
             Ok(())
         })
@@ -683,6 +728,41 @@ impl<J: RawJournalAdapter> RawJournalReader<J> {
             }
         }
     }
+    #[allow(unused)]
+    pub fn verify(
+        file: SdssFile<<J as RawJournalAdapter>::Spec>,
+        gs: &J::GlobalState,
+    ) -> RuntimeResult<JournalVerifyResult> {
+        let reader = TrackedReader::with_cursor(
+            file,
+            <<J as RawJournalAdapter>::Spec as FileSpecV1>::SIZE as u64,
+        )?;
+        let mut me = Self::new(reader, 0, 0, 0, 0);
+        let file_length = me.tr.cursor() + me.tr.remaining();
+        let mut last_valid_cursor = me.tr.cursor();
+        let (closed, corrupted) = loop {
+            if me.tr.is_eof() {
+                // not corrupted, but the journal was never closed
+                break (false, false);
+            }
+            match me._apply_next_event_and_stop(gs) {
+                Ok(stop) => {
+                    last_valid_cursor = me.tr.cursor();
+                    if stop {
+                        break (true, false);
+                    }
+                }
+                Err(_) => break (false, true),
+            }
+        };
+        let trailing_bytes = file_length - last_valid_cursor;
+        Ok(JournalVerifyResult {
+            valid_events: (me.stats.server_events + me.stats.driver_events) as u64,
+            corrupt_events: corrupted as u64,
+            trailing_bytes,
+            is_clean: closed & !corrupted & (trailing_bytes == 0),
+        })
+    }
     fn new(
         reader: TrackedReader<<J as RawJournalAdapter>::Spec>,
         txn_id: u64,
@@ -26,14 +26,18 @@
 
 use {
     super::{
-        create_journal, open_journal, CommitPreference, DriverEvent, DriverEventKind,
-        JournalInitializer, RawJournalAdapter, RawJournalAdapterEvent, RawJournalWriter,
+        create_journal, open_journal, verify_journal, CommitPreference, DriverEvent,
+        DriverEventKind, JournalInitializer, JournalVerifyResult, RawJournalAdapter,
+        RawJournalAdapterEvent, RawJournalWriter,
     },
     crate::engine::{
         error::StorageError,
         fractal::error::ErrorContext,
         storage::{
-            common::sdss::sdss_r1::rw::TrackedReader,
+            common::{
+                interface::fs::{File, FileExt, FileSystem, FileWrite, FileWriteExt},
+                sdss::sdss_r1::rw::{TrackedReader, TrackedWriter},
+            },
             v2::raw::{
                 journal::raw::{JournalReaderTraceEvent, JournalWriterTraceEvent},
                 spec::SystemDatabaseV1,
@@ -189,10 +193,64 @@ impl RawJournalAdapter for SimpleDBJournal {
     }
 }
 
+/// Same as [`SimpleDBJournal`], but events are written directly into the writer's buffer and aren't synced on commit
+pub struct UnsyncedDBJournal;
+
+impl<T: SimpleDBEvent> RawJournalAdapterEvent<UnsyncedDBJournal> for T {
+    fn md(&self) -> u64 {
+        T::OPC as _
+    }
+    fn write_direct(self, w: &mut TrackedWriter<SystemDatabaseV1>, _: ()) -> RuntimeResult<()> {
+        let mut buf = vec![];
+        T::write_buffered(self, &mut buf);
+        w.tracked_write(&buf).map_err(From::from)
+    }
+}
+
+impl RawJournalAdapter for UnsyncedDBJournal {
+    const AUTO_SYNC_ON_EVENT_COMMIT: bool = false;
+    const COMMIT_PREFERENCE: CommitPreference = CommitPreference::Direct;
+    type Spec = SystemDatabaseV1;
+    type GlobalState = SimpleDB;
+    type EventMeta = EventMeta;
+    type CommitContext = ();
+    type Context<'a> = () where Self: 'a;
+    fn initialize(_: &JournalInitializer) -> Self {
+        Self
+    }
+    fn enter_context<'a>(_: &'a mut RawJournalWriter<Self>) -> Self::Context<'a> {
+        ()
+    }
+    fn parse_event_meta(meta: u64) -> Option<Self::EventMeta> {
+        SimpleDBJournal::parse_event_meta(meta)
+    }
+    fn commit_direct<E: RawJournalAdapterEvent<Self>>(
+        &mut self,
+        w: &mut TrackedWriter<Self::Spec>,
+        event: E,
+        ctx: (),
+    ) -> RuntimeResult<()> {
+        event.write_direct(w, ctx)
+    }
+    fn decode_apply<'a>(
+        gs: &Self::GlobalState,
+        meta: Self::EventMeta,
+        file: &mut TrackedReader<Self::Spec>,
+    ) -> RuntimeResult<()> {
+        SimpleDBJournal::decode_apply(gs, meta, file)
+    }
+}
+
 /*
     journal tests
 */
 
+#[test]
+fn driver_event_kind_display() {
+    assert_eq!(DriverEventKind::Reopened.to_string(), "Reopened");
+    assert_eq!(DriverEventKind::Closed.to_string(), "Closed");
+}
+
 #[test]
 fn journal_open_close() {
     const JOURNAL_NAME: &str = "journal_open_close";
@@ -466,3 +524,103 @@ fn multi_boot() {
         RawJournalWriter::close_driver(&mut j).unwrap();
     }
 }
+
+#[test]
+fn verify_clean() {
+    {
+        let mut j = create_journal::<SimpleDBJournal>("verify_clean").unwrap();
+        let mut db = SimpleDB::new();
+        db.push(&mut j, "key_a").unwrap();
+        db.push(&mut j, "key_b").unwrap();
+        RawJournalWriter::close_driver(&mut j).unwrap();
+    }
+    {
+        let db = SimpleDB::new();
+        let mut j = open_journal::<SimpleDBJournal>("verify_clean", &db).unwrap();
+        RawJournalWriter::close_driver(&mut j).unwrap();
+    }
+    // 2 pushes + close + reopen + close
+    assert_eq!(
+        verify_journal::<SimpleDBJournal>("verify_clean", &SimpleDB::new()).unwrap(),
+        JournalVerifyResult {
+            valid_events: 5,
+            corrupt_events: 0,
+            trailing_bytes: 0,
+            is_clean: true,
+        }
+    );
+}
+
+#[test]
+fn verify_corrupted() {
+    {
+        let mut j = create_journal::<SimpleDBJournal>("verify_corrupted").unwrap();
+        let mut db = SimpleDB::new();
+        db.push(&mut j, "key_a").unwrap();
+        db.push(&mut j, "key_b").unwrap();
+        RawJournalWriter::close_driver(&mut j).unwrap();
+    }
+    // flip the last byte of the second event. it still decodes, but the checksum in the close event won't match
+    let mut f = File::open("verify_corrupted").unwrap();
+    let file_length = f.f_len().unwrap();
+    f.f_seek_start(file_length - DriverEvent::FULL_EVENT_SIZE as u64 - 1)
+        .unwrap();
+    f.fwrite_all(b"!").unwrap();
+    f.fsync_all().unwrap();
+    drop(f);
+    assert_eq!(
+        verify_journal::<SimpleDBJournal>("verify_corrupted", &SimpleDB::new()).unwrap(),
+        JournalVerifyResult {
+            valid_events: 2,
+            corrupt_events: 1,
+            trailing_bytes: DriverEvent::FULL_EVENT_SIZE as u64,
+            is_clean: false,
+        }
+    );
+}
+
+#[test]
+fn verify_unclosed() {
+    {
+        let mut j = create_journal::<SimpleDBJournal>("verify_unclosed").unwrap();
+        let mut db = SimpleDB::new();
+        db.push(&mut j, "key_a").unwrap();
+        // crash without closing
+    }
+    assert_eq!(
+        verify_journal::<SimpleDBJournal>("verify_unclosed", &SimpleDB::new()).unwrap(),
+        JournalVerifyResult {
+            valid_events: 1,
+            corrupt_events: 0,
+            trailing_bytes: 0,
+            is_clean: false,
+        }
+    );
+}
+
+#[test]
+fn unsynced_commits_flush_when_buffer_fills() {
+    const JOURNAL_NAME: &str = "unsynced_commits_flush_when_buffer_fills";
+    // txn id (16B) + metadata (8B) + key length (8B) + key (992B) = 1024 bytes per event
+    let key = "k".repeat(992);
+    {
+        let mut j = create_journal::<UnsyncedDBJournal>(JOURNAL_NAME).unwrap();
+        for _ in 0..4 {
+            j.commit_event(DbEventPush(&key)).unwrap();
+        }
+        // still fits
+        assert_eq!(j.log_file.unflushed_bytes(), 4096);
+        j.commit_event(DbEventPush(&key)).unwrap();
+        // the buffer crossed the limit so everything must have reached the file (before any close)
+        assert_eq!(j.log_file.unflushed_bytes(), 0);
+        assert_eq!(
+            FileSystem::read(JOURNAL_NAME).unwrap().len() as u64,
+            j.log_file.cursor()
+        );
+        RawJournalWriter::close_driver(&mut j).unwrap();
+    }
+    let db = SimpleDB::new();
+    let mut j = open_journal::<UnsyncedDBJournal>(JOURNAL_NAME, &db).unwrap();
+    assert_eq!(db.data().as_ref(), vec![key; 5]);
+    RawJournalWriter::close_driver(&mut j).unwrap();
+}
@@ -96,6 +96,17 @@ impl<T: Pointable> Atomic<T> {
     }
 }
 
+impl<T: Clone> Atomic<T> {
+    /// Load the value and return an owned clone of it, or `None` if the pointer is null
+    #[allow(unused)]
+    pub fn load_cloned(&self, g: &Guard) -> Option<T> {
+        unsafe {
+            // UNSAFE(@ohsayan): the guard keeps the value alive until we're done cloning it
+            self.ld_acq(g).as_ref().cloned()
+        }
+    }
+}
+
 impl<T, A> From<A> for Atomic<T>
 where
     A: Into<CBAtomic<T>>,
@@ -111,3 +122,16 @@ impl<T> Deref for Atomic<T> {
         &self.a
     }
 }
+
+#[test]
+fn load_cloned() {
+    let g = cpin();
+    let a: Atomic<String> = Atomic::null();
+    assert_eq!(a.load_cloned(&g), None);
+    let a: Atomic<String> = Atomic::from(Owned::new(String::from("hello")));
+    assert_eq!(a.load_cloned(&g).as_deref(), Some("hello"));
+    unsafe {
+        // UNSAFE(@ohsayan): we're the only owner
+        drop(a.ld_acq(&g).into_owned());
+    }
+}
@@ -155,6 +155,67 @@ fn parse_validate_cli_args_help_and_version() {
     );
 }
 
+#[test]
+fn validate_consistency_endpoint_conflict() {
+    let cfg = |tls_port| {
+        Configuration::new(
+            ConfigEndpoint::Multi(
+                ConfigEndpointTcp::new("127.0.0.1".into(), 2003),
+                ConfigEndpointTls::new(
+                    ConfigEndpointTcp::new("127.0.0.1".into(), tls_port),
+                    "".into(),
+                    "".into(),
+                    "".into(),
+                ),
+            ),
+            ConfigMode::Dev,
+            ConfigSystem::new(600),
+            ConfigAuth::new(AuthDriver::Pwd, "password12345678".into()),
+        )
+    };
+    assert!(cfg(2004).validate_consistency().is_ok());
+    assert!(cfg(2003).validate_consistency().is_err());
+}
+
+#[test]
+fn config_to_yaml_redacts_secrets() {
+    let cfg = Configuration::new(
+        ConfigEndpoint::Multi(
+            ConfigEndpointTcp::new("127.0.0.1".into(), 2003),
+            ConfigEndpointTls::new(
+                ConfigEndpointTcp::new("127.0.0.1".into(), 2004),
+                "cert".into(),
+                "key".into(),
+                "pass".into(),
+            ),
+        ),
+        ConfigMode::Prod,
+        ConfigSystem::new(600),
+        ConfigAuth::new(AuthDriver::Pwd, "password12345678".into()),
+    );
+    assert_eq!(
+        cfg.to_yaml(),
+        "\
+system:
+  mode: prod
+  rs_window: 600
+auth:
+  plugin: pwd
+  root_pass: <redacted>
+endpoints:
+  secure:
+    host: 127.0.0.1
+    port: 2004
+    cert: <redacted>
+    private_key: <redacted>
+    pkey_passphrase: <redacted>
+  insecure:
+    host: 127.0.0.1
+    port: 2003
+"
+    );
+}
+
 /*
     env tests
 */
@@ -247,6 +308,23 @@ fn parse_validate_env_args() {
         },
     );
 }
+#[test]
+fn config_env_uses_defaults() {
+    config::set_env_src(vec![
+        "SKYDB_AUTH_ROOT_PASSWORD=password12345678".into(),
+        "SKYDB_RUN_MODE=prod".into(),
+    ]);
+    let cfg = config::check_configuration().unwrap().into_config();
+    assert_eq!(
+        cfg,
+        Configuration::new(
+            ConfigEndpoint::Insecure(ConfigEndpointTcp::new("127.0.0.1".into(), 2003)),
+            ConfigMode::Prod,
+            ConfigSystem::new(crate::engine::fractal::GENERAL_EXECUTOR_WINDOW),
+            ConfigAuth::new(AuthDriver::Pwd, "password12345678".into())
+        )
+    );
+}
 const CONFIG_FILE: &str = "\
 system:
   mode: dev
@@ -299,3 +377,111 @@ fn test_config_file() {
         },
     )
 }
+#[test]
+fn config_file_env_overrides() {
+    config::set_cli_src(vec!["skyd".into(), "--config=config.yml".into()]);
+    config::set_file_src(
+        "\
+system:
+  mode: dev
+  rs_window: 600
+
+auth:
+  plugin: pwd
+  root_pass: password12345678
+
+endpoints:
+  insecure:
+    host: 127.0.0.1
+    port: 2003
+",
+    );
+    config::set_env_src(vec![
+        "SKYDB_RUN_MODE=prod".into(),
+        "SKYDB_ENDPOINTS=tcp@localhost:8080".into(),
+    ]);
+    let cfg = config::check_configuration().unwrap().into_config();
+    assert_eq!(
+        cfg,
+        Configuration::new(
+            ConfigEndpoint::Insecure(ConfigEndpointTcp::new("localhost".into(), 8080)),
+            ConfigMode::Prod,
+            ConfigSystem::new(600),
+            ConfigAuth::new(AuthDriver::Pwd, "password12345678".into())
+        )
+    )
+}
+
+fn config_str_err(cfg: &str) -> String {
+    config::parse_config_str(cfg).unwrap_err().to_string()
+}
+
+#[test]
+fn config_str_simple() {
+    let cfg = config::parse_config_str(
+        "\
+auth:
+  plugin: pwd
+  root_pass: password12345678
+",
+    )
+    .unwrap()
+    .into_config();
+    assert_eq!(
+        cfg,
+        Configuration::new(
+            ConfigEndpoint::Insecure(ConfigEndpointTcp::new("127.0.0.1".into(), 2003)),
+            ConfigMode::Dev,
+            ConfigSystem::new(crate::engine::fractal::GENERAL_EXECUTOR_WINDOW),
+            ConfigAuth::new(AuthDriver::Pwd, "password12345678".into())
+        )
+    );
+}
+
+#[test]
+fn config_str_bad_yaml() {
+    assert!(config_str_err("auth: [").contains("failed to parse YAML config file"));
+}
+
+#[test]
+fn config_str_missing_auth() {
+    assert!(config_str_err("system:\n  mode: prod\n")
+        .contains("root account must be configured with auth.root_password"));
+}
+
+#[test]
+fn config_str_short_root_password() {
+    assert!(
+        config_str_err("auth:\n  plugin: pwd\n  root_pass: password\n")
+            .contains("the root password must have at least 16 characters")
+    );
+}
+
+#[test]
+fn config_str_zero_service_window() {
+    assert!(config_str_err(
+        "system:\n  rs_window: 0\nauth:\n  plugin: pwd\n  root_pass: password12345678\n"
+    )
+    .contains("invalid value for service window. must be nonzero"));
+}
+
+#[test]
+fn config_str_endpoint_conflict() {
+    let cfg = "\
+auth:
+  plugin: pwd
+  root_pass: password12345678
+endpoints:
+  secure:
+    host: 127.0.0.1
+    port: 2003
+    cert: cert.pem
+    private_key: private.key
+    pkey_passphrase: passphrase.txt
+  insecure:
+    host: 127.0.0.1
+    port: 2003
+";
+    assert!(config_str_err(cfg)
+        .contains("the TCP and TLS endpoints cannot listen on the same host and port"));
+}
@@ -39,3 +39,79 @@ fn inspect_global_as_std_user_does_not_return_user_info() {
     let inspect: String = db.query_parse(&query!("inspect global")).unwrap();
     assert!(!inspect.contains("\"users\":"));
 }
+
+#[dbtest(expect_error = 100)]
+fn expect_error_with_code() {
+    let mut db = db!();
+    db.query_parse::<()>(&query!("inspect space expect_error_nospace"))
+}
+
+#[dbtest(expect_error = "QueryError::QExecObjectNotFound")]
+fn expect_error_with_name() {
+    let mut db = db!();
+    db.query_parse::<()>(&query!("drop space expect_error_nospace"))
+}
+
+#[sky_macros::dbtest_fixture]
+fn create_fixture_space(db: &mut skytable::Connection) {
+    db.query_parse::<()>(&query!("create space if not exists fixture_space"))
+        .unwrap();
+}
+
+#[sky_macros::dbtest_module(teardown = "drop_fixture_space")]
+mod fixture_space {
+    use {sky_macros::dbtest, skytable::query};
+    fn drop_fixture_space(db: &mut skytable::Connection) {
+        db.query_parse::<()>(&query!("drop space allow not empty fixture_space"))
+            .unwrap();
+    }
+    #[dbtest(fixture = "super::create_fixture_space")]
+    fn fixture_runs_before_test() {
+        let mut db = db!();
+        let inspect: String = db
+            .query_parse(&query!("inspect space fixture_space"))
+            .unwrap();
+        assert!(inspect.contains("\"models\":"));
+    }
+}
+
+#[sky_macros::dbtest_module(setup = "create_model", teardown = "drop_space")]
+mod inspect_shared_model {
+    use {super::super::TestConnection, sky_macros::dbtest, skytable::query};
+    fn create_model(db: &mut skytable::Connection) {
+        // the teardown is skipped if an earlier run was filtered or failed, so clear out what it may have left
+        db.query_parse::<()>(&query!(
+            "drop space if exists allow not empty inspect_shared"
+        ))
+        .unwrap();
+        db.query_parse::<()>(&query!("create space inspect_shared"))
+            .unwrap();
+        db.query_parse::<()>(&query!(
+            "create model inspect_shared.mymodel(username: string, password: binary)"
+        ))
+        .unwrap();
+    }
+    fn drop_space(db: &mut skytable::Connection) {
+        db.query_parse::<()>(&query!("drop space allow not empty inspect_shared"))
+            .unwrap();
+    }
+    #[dbtest]
+    fn inspect_space_lists_model() {
+        let mut db = db!();
+        let inspect: String = db
+            .query_parse(&query!("inspect space inspect_shared"))
+            .unwrap();
+        assert!(inspect.contains("\"mymodel\""));
+    }
+    #[dbtest]
+    fn inspect_missing_model() {
+        TestConnection::new(db!()).assert_not_found("inspect model inspect_shared.nomodel");
+    }
+    #[dbtest]
+    fn inspect_model_returns_decl() {
+        TestConnection::new(db!()).assert_string(
+            "inspect model inspect_shared.mymodel",
+            r#"{"decl":"{*username:String,!password:Binary}","rows":0,"properties":{}}"#,
+        );
+    }
+}
@@ -0,0 +1,83 @@
+/*
+ * Created on Sat Oct 17 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+#[sky_macros::dbtest_module(setup = "create_model", teardown = "drop_space")]
+mod query_builder {
+    use {
+        sky_macros::{dbtest, QueryBuilder},
+        skytable::{
+            query,
+            response::{Response, Value},
+        },
+    };
+    fn create_model(db: &mut skytable::Connection) {
+        // the teardown is skipped if an earlier run was filtered or failed, so clear out what it may have left
+        db.query_parse::<()>(&query!(
+            "drop space if exists allow not empty query_builder"
+        ))
+        .unwrap();
+        db.query_parse::<()>(&query!("create space query_builder"))
+            .unwrap();
+        db.query_parse::<()>(&query!(
+            "create model query_builder.users(username: string, visits: uint64)"
+        ))
+        .unwrap();
+    }
+    fn drop_space(db: &mut skytable::Connection) {
+        db.query_parse::<()>(&query!("drop space allow not empty query_builder"))
+            .unwrap();
+    }
+    #[derive(QueryBuilder)]
+    #[model(space = "query_builder", model = "users")]
+    #[allow(unused)]
+    struct User {
+        username: String,
+        visits: u64,
+    }
+    #[dbtest]
+    fn insert_with_builder() {
+        let mut db = db!();
+        // setters can be called in any order
+        User::insert_builder()
+            .visits(10)
+            .username("sayan".into())
+            .build(&mut db)
+            .unwrap();
+        let row = db
+            .query(&query!(
+                "select * from query_builder.users where username = ?",
+                "sayan"
+            ))
+            .unwrap();
+        match row {
+            Response::Row(r) => assert_eq!(
+                r.into_values(),
+                vec![Value::String("sayan".into()), Value::UInt64(10)]
+            ),
+            resp => panic!("expected a row but got {resp:?}"),
+        }
+    }
+}
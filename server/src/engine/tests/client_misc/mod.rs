@@ -25,5 +25,51 @@
 */
 
 mod ddl;
+mod dml;
 mod sec;
 mod sysctl;
+
+use {
+    crate::engine::error::QueryError,
+    skytable::{error::Error, query},
+};
+
+const NOT_FOUND_ERR: u16 = QueryError::QExecObjectNotFound.value_u8() as u16;
+
+/// A client connection with a few assertion helpers to cut down on boilerplate in tests
+pub struct TestConnection(skytable::Connection);
+
+impl TestConnection {
+    pub fn new(db: skytable::Connection) -> Self {
+        Self(db)
+    }
+    /// Assert that the query runs successfully and returns an empty response
+    #[track_caller]
+    pub fn assert_okay(&mut self, q: &str) {
+        if let Err(e) = self.0.query_parse::<()>(&query!(q)) {
+            panic!("expected `{q}` to succeed but got {e:?}");
+        }
+    }
+    /// Assert that the query returns the expected string
+    #[track_caller]
+    pub fn assert_string(&mut self, q: &str, expected: &str) {
+        match self.0.query_parse::<String>(&query!(q)) {
+            Ok(s) => assert_eq!(s, expected, "unexpected response for `{q}`"),
+            Err(e) => panic!("expected `{q}` to return a string but got {e:?}"),
+        }
+    }
+    /// Assert that the query fails because the object it refers to doesn't exist
+    #[track_caller]
+    pub fn assert_not_found(&mut self, q: &str) {
+        self.assert_server_error(q, NOT_FOUND_ERR)
+    }
+    /// Assert that the query fails with the given server error code
+    #[track_caller]
+    pub fn assert_server_error(&mut self, q: &str, code: u16) {
+        match self.0.query_parse::<()>(&query!(q)) {
+            Err(Error::ServerError(c)) if c == code => {}
+            Err(e) => panic!("expected `{q}` to fail with server error {code} but got {e:?}"),
+            Ok(()) => panic!("expected `{q}` to fail with server error {code} but it succeeded"),
+        }
+    }
+}
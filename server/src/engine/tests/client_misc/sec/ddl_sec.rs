@@ -158,3 +158,29 @@ fn ensure_drop_model_end_of_tokens() {
         Error::ServerError(INVALID_SYNTAX_ERR)
     );
 }
+
+#[sky_macros::dbtest_module(setup = "create_user", teardown = "drop_user")]
+mod std_user {
+    use {sky_macros::dbtest, skytable::query};
+    fn create_user(db: &mut skytable::Connection) {
+        // the user may be left over if an earlier run skipped the teardown
+        let _ = db.query_parse::<()>(&query!("sysctl drop user ddl_sec_user"));
+        db.query_parse::<()>(&query!(
+            "sysctl create user ddl_sec_user with { password: ? }",
+            "ddl_sec_password"
+        ))
+        .unwrap();
+    }
+    fn drop_user(db: &mut skytable::Connection) {
+        db.query_parse::<()>(&query!("sysctl drop user ddl_sec_user"))
+            .unwrap();
+    }
+    #[dbtest(
+        auth = "ddl_sec_user:ddl_sec_password",
+        expect_error = "SysPermissionDenied"
+    )]
+    fn deny_drop_model_for_std_user() {
+        let mut db = db!();
+        db.query_parse::<()>(&query!("drop model myspace.mymodel"))
+    }
+}
@@ -25,17 +25,42 @@
 */
 
 mod status {
-    use {sky_macros::dbtest, skytable::query};
+    use {super::super::TestConnection, sky_macros::dbtest};
     #[dbtest]
     fn check_status_root() {
-        let mut db = db!();
-        db.query_parse::<()>(&query!("sysctl report status"))
-            .unwrap();
+        TestConnection::new(db!()).assert_okay("sysctl report status");
     }
     #[dbtest(switch_user(username = "user1"))]
     fn check_status_standard_user() {
+        TestConnection::new(db!()).assert_okay("sysctl report status");
+    }
+}
+
+mod config {
+    use {super::super::TestConnection, sky_macros::dbtest, skytable::query};
+    #[dbtest]
+    fn report_config_root() {
+        let mut db = db!();
+        let cfg: String = db.query_parse(&query!("sysctl report config")).unwrap();
+        assert!(cfg.contains("root_pass: <redacted>"));
+    }
+    #[dbtest(switch_user(username = "user1"))]
+    fn report_config_standard_user() {
+        TestConnection::new(db!()).assert_server_error("sysctl report config", 5);
+    }
+}
+
+mod stats {
+    use {super::super::TestConnection, sky_macros::dbtest, skytable::query};
+    #[dbtest]
+    fn report_stats_counts_self() {
         let mut db = db!();
-        db.query_parse::<()>(&query!("sysctl report status"))
-            .unwrap();
+        let stats: String = db.query_parse(&query!("sysctl report stats")).unwrap();
+        assert!(!stats.contains("\"active_connections\":0"));
+        assert!(stats.contains("\"uptime_secs\":"));
+    }
+    #[dbtest(switch_user(username = "user1"))]
+    fn report_stats_standard_user() {
+        TestConnection::new(db!()).assert_server_error("sysctl report stats", 5);
     }
 }
@@ -29,3 +29,23 @@ mod macros;
 mod cfg;
 mod client;
 mod client_misc;
+
+#[test]
+fn ready_notifier_wakes_waiters() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let notifier = super::ReadyNotifier::new();
+    rt.block_on(async {
+        let waiter = tokio::spawn({
+            let notifier = notifier.clone();
+            async move { notifier.ready().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+        notifier.notify();
+        waiter.await.unwrap();
+        // already ready, so this must not block
+        notifier.ready().await;
+    });
+}
@@ -60,6 +60,8 @@ const TEXT: &str = "
 
 type IoResult<T> = std::io::Result<T>;
 const SKY_PID_FILE: &str = ".sky_pid";
+/// the maximum time we wait for each storage component to close on exit
+const SKY_FINISH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
 
 fn main() {
     use crate::engine::config::ConfigReturn;
@@ -81,10 +83,20 @@ fn main() {
 fn entrypoint(config: engine::config::Configuration) {
     println!("{TEXT}\nSkytable v{VERSION} | {URL}\n");
     let run = || {
+        engine::set_context_init(span!("starting server"));
         let f_rt_start = || {
-            engine::set_context_init("locking PID file");
-            let pid_file = util::os::FileLock::new(SKY_PID_FILE)?;
-            engine::set_context_init("initializing runtime");
+            let span = engine::enter_context_init(span!("locking PID file", path = SKY_PID_FILE));
+            let pid_file = match util::os::FileLock::new(SKY_PID_FILE) {
+                Ok(pf) => pf,
+                Err(e) => {
+                    if let Ok(Some(pid)) = util::os::FileLock::read_pid(SKY_PID_FILE) {
+                        error!("skyd is already running with PID {pid}");
+                    }
+                    return Err(e.into());
+                }
+            };
+            drop(span);
+            let _span = engine::enter_context_init(span!("initializing runtime"));
             let runtime = tokio::runtime::Builder::new_multi_thread()
                 .thread_name("server")
                 .enable_all()
@@ -96,9 +108,10 @@ fn entrypoint(config: engine::config::Configuration) {
             Err(e) => return (None, None, Err(e)),
         };
         let f_glob_init = runtime.block_on(async move {
-            engine::set_context_init("binding system signals");
+            let span = engine::enter_context_init(span!("binding system signals"));
             let signal = util::os::TerminationSignal::init()?;
-            let (config, global) = tokio::task::spawn_blocking(|| engine::load_all(config))
+            drop(span);
+            let (config, global) = tokio::task::spawn_blocking(|| engine::load_all(config, |_| {}))
                 .await
                 .unwrap()?;
             engine::RuntimeResult::Ok((signal, config, global))
@@ -108,22 +121,43 @@ fn entrypoint(config: engine::config::Configuration) {
             Err(e) => return (Some(pid_file), None, Err(e)),
         };
         let g = global.global.clone();
-        let result_start =
-            runtime.block_on(async move { engine::start(signal, config, global).await });
+        let result_start = runtime.block_on(async move {
+            engine::start(signal, engine::ReadyNotifier::new(), config, global).await
+        });
         (Some(pid_file), Some(g), result_start)
     };
     let (pid_file, global, result) = run();
+    let mut finished_cleanly = true;
+    let mut keep_pid_file = false;
     if let Some(g) = global {
         info!("cleaning up data");
-        engine::finish(g);
+        match engine::finish_with_timeout(g, SKY_FINISH_TIMEOUT) {
+            engine::FinishResult::Clean => {}
+            engine::FinishResult::Failed(errors) => {
+                errors.iter().for_each(|e| error!("{e}"));
+                finished_cleanly = false;
+            }
+            engine::FinishResult::Abandoned(errors) => {
+                errors.iter().for_each(|e| error!("{e}"));
+                error!("some components may still be writing to disk. not removing PID file");
+                finished_cleanly = false;
+                keep_pid_file = true;
+            }
+        }
     }
-    if let Some(_) = pid_file {
+    if pid_file.is_some() & !keep_pid_file {
         if let Err(e) = std::fs::remove_file(SKY_PID_FILE) {
             error!("failed to remove PID file: {e}");
         }
     }
     match result {
-        Ok(()) => println!("goodbye"),
-        Err(e) => exit_fatal!(error!("{e}")),
+        Ok(()) if finished_cleanly => println!("goodbye"),
+        Ok(()) => exit_fatal!(error!("failed to cleanly shut down the storage engine")),
+        Err(e) => exit_fatal!({
+            error!("{e}");
+            if let Some(description) = e.description() {
+                error!("{description}");
+            }
+        }),
     }
 }
@@ -0,0 +1,115 @@
+/*
+ * Created on Sat Oct 17 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+/*
+    binary codec for event payloads
+    ---
+    integers are encoded in little endian, while strings and byte vectors are prefixed with their length as a u64
+*/
+
+/// A type that can be encoded into (and decoded from) a byte buffer. Use `#[derive(SkyCodec)]` for structs
+pub trait SkyEncodable: Sized {
+    /// Append the encoded form of self to `buf`
+    fn encode_to(&self, buf: &mut Vec<u8>);
+    /// Decode a value from the start of `buf`, returning it along with the unconsumed part of the buffer
+    fn decode_from(buf: &[u8]) -> Option<(Self, &[u8])>;
+}
+
+macro_rules! impl_sky_encodable_int {
+    ($($ty:ty),*) => {
+        $(impl SkyEncodable for $ty {
+            fn encode_to(&self, buf: &mut Vec<u8>) {
+                buf.extend_from_slice(&self.to_le_bytes());
+            }
+            fn decode_from(buf: &[u8]) -> Option<(Self, &[u8])> {
+                if buf.len() < core::mem::size_of::<Self>() {
+                    return None;
+                }
+                let (int, rem) = buf.split_at(core::mem::size_of::<Self>());
+                Some((Self::from_le_bytes(int.try_into().unwrap()), rem))
+            }
+        })*
+    };
+}
+
+impl_sky_encodable_int!(u8, u16, u32, u64, u128);
+
+fn decode_len_prefixed(buf: &[u8]) -> Option<(&[u8], &[u8])> {
+    let (len, buf) = u64::decode_from(buf)?;
+    if (buf.len() as u64) < len {
+        return None;
+    }
+    Some(buf.split_at(len as usize))
+}
+
+impl SkyEncodable for Vec<u8> {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        (self.len() as u64).encode_to(buf);
+        buf.extend_from_slice(self);
+    }
+    fn decode_from(buf: &[u8]) -> Option<(Self, &[u8])> {
+        decode_len_prefixed(buf).map(|(data, rem)| (data.to_vec(), rem))
+    }
+}
+
+impl SkyEncodable for String {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        (self.len() as u64).encode_to(buf);
+        buf.extend_from_slice(self.as_bytes());
+    }
+    fn decode_from(buf: &[u8]) -> Option<(Self, &[u8])> {
+        let (data, rem) = decode_len_prefixed(buf)?;
+        let string = core::str::from_utf8(data).ok()?;
+        Some((string.to_owned(), rem))
+    }
+}
+
+#[cfg(test)]
+#[derive(Debug, PartialEq, sky_macros::SkyCodec)]
+struct TestEvent {
+    id: u128,
+    kind: u8,
+    name: String,
+    payload: Vec<u8>,
+}
+
+#[test]
+fn derived_codec_roundtrip() {
+    let ev = TestEvent {
+        id: u128::MAX,
+        kind: 2,
+        name: "sayan".into(),
+        payload: vec![1, 2, 3],
+    };
+    let mut buf = vec![];
+    ev.encode_to(&mut buf);
+    buf.push(0xFF);
+    let (decoded, rem) = TestEvent::decode_from(&buf).unwrap();
+    assert_eq!(decoded, ev);
+    assert_eq!(rem, [0xFF]);
+    // truncated buffers don't decode
+    assert!(TestEvent::decode_from(&buf[..buf.len() - 2]).is_none());
+}
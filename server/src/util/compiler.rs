@@ -51,6 +51,12 @@ pub fn cold_call<U>(v: impl FnOnce() -> U) -> U {
     v()
 }
 
+/// Erase the type of a cold closure so that the (usually large) recovery path is not monomorphized into every
+/// caller. Use with [`cold_call`]
+pub fn cold_box<'a, T>(f: impl FnOnce() -> T + 'a) -> Box<dyn FnOnce() -> T + 'a> {
+    Box::new(f)
+}
+
 #[cold]
 #[inline(never)]
 pub const fn cold_val<T>(v: T) -> T {
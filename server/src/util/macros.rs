@@ -76,6 +76,14 @@ macro_rules! cfg_test {
     };
 }
 
+#[macro_export]
+/// Create a context frame for the engine's span chain. Fields are recorded as `key=value`
+macro_rules! span {
+    ($msg:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        $crate::engine::ContextFrame::new($msg, ::std::vec![$((::core::stringify!($key), ::std::string::ToString::to_string(&$value))),*])
+    };
+}
+
 #[macro_export]
 /// Compare two vectors irrespective of their elements' position
 macro_rules! veceq_transposed {
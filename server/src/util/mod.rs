@@ -26,6 +26,7 @@
 
 #[macro_use]
 mod macros;
+pub mod codec;
 pub mod compiler;
 pub mod os;
 #[cfg(test)]
@@ -36,9 +36,13 @@ use {
     std::{
         ffi::OsStr,
         fmt, fs,
+        future::Future,
         path::Path,
+        pin::Pin,
+        task::{Context, Poll},
         time::{SystemTime, UNIX_EPOCH},
     },
+    tokio::sync::oneshot,
 };
 pub use {flock::FileLock, free_memory::free_memory_in_bytes};
 
@@ -81,6 +85,43 @@ impl PartialEq for SysIOError {
     }
 }
 
+/// A future that resolves when the server has been asked to terminate
+pub struct TerminationSignal {
+    source: TerminationSource,
+}
+
+enum TerminationSource {
+    Os(OsTerminationSignal),
+    Custom(oneshot::Receiver<()>),
+}
+
+impl TerminationSignal {
+    /// Register handlers for the OS termination signals
+    pub fn init() -> IoResult<Self> {
+        OsTerminationSignal::init().map(|sig| Self {
+            source: TerminationSource::Os(sig),
+        })
+    }
+    #[allow(unused)]
+    /// Use the given channel as the termination signal instead of registering OS signal handlers. This is meant
+    /// for embedders that manage signals themselves: the signal fires when a value is sent or the sender is dropped
+    pub fn with_custom_handler(rx: oneshot::Receiver<()>) -> Self {
+        Self {
+            source: TerminationSource::Custom(rx),
+        }
+    }
+}
+
+impl Future for TerminationSignal {
+    type Output = Option<()>;
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
+        match &mut self.source {
+            TerminationSource::Os(sig) => Pin::new(sig).poll(ctx),
+            TerminationSource::Custom(rx) => Pin::new(rx).poll(ctx).map(Result::ok),
+        }
+    }
+}
+
 #[cfg(unix)]
 mod unix {
     use {
@@ -142,12 +183,12 @@ mod unix {
         let _ = ResourceLimit::get().unwrap();
     }
 
-    pub struct TerminationSignal {
+    pub struct OsTerminationSignal {
         sigint: Signal,
         sigterm: Signal,
     }
 
-    impl TerminationSignal {
+    impl OsTerminationSignal {
         pub fn init() -> crate::IoResult<Self> {
             let sigint = signal(SignalKind::interrupt())?;
             let sigterm = signal(SignalKind::terminate())?;
@@ -155,7 +196,7 @@ mod unix {
         }
     }
 
-    impl Future for TerminationSignal {
+    impl Future for OsTerminationSignal {
         type Output = Option<()>;
         fn poll(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
             let int = self.sigint.poll_recv(ctx);
@@ -180,18 +221,18 @@ mod windows {
         tokio::signal::windows::{ctrl_break, ctrl_c, CtrlBreak, CtrlC},
     };
 
-    pub struct TerminationSignal {
+    pub struct OsTerminationSignal {
         ctrl_c: CtrlC,
         ctrl_break: CtrlBreak,
     }
-    impl TerminationSignal {
+    impl OsTerminationSignal {
         pub fn init() -> crate::IoResult<Self> {
             let ctrl_c = ctrl_c()?;
             let ctrl_break = ctrl_break()?;
             Ok(Self { ctrl_c, ctrl_break })
         }
     }
-    impl Future for TerminationSignal {
+    impl Future for OsTerminationSignal {
         type Output = Option<()>;
         fn poll(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
             let ctrl_c = self.ctrl_c.poll_recv(ctx);
@@ -560,3 +601,33 @@ fn t_uptime() {
             <= (Duration::from_millis(uptime_1.try_into().unwrap()) + Duration::from_secs(10))
     )
 }
+
+#[test]
+fn flock_read_pid() {
+    const PID_FILE: &str = "__flock_read_pid_test";
+    let lock = FileLock::new(PID_FILE).unwrap();
+    assert_eq!(
+        FileLock::read_pid(PID_FILE).unwrap(),
+        Some(std::process::id())
+    );
+    // a second lock must fail without clobbering the PID
+    assert!(FileLock::new(PID_FILE).is_err());
+    assert_eq!(
+        FileLock::read_pid(PID_FILE).unwrap(),
+        Some(std::process::id())
+    );
+    lock.release().unwrap();
+    fs::remove_file(PID_FILE).unwrap();
+    assert_eq!(FileLock::read_pid(PID_FILE).unwrap(), None);
+}
+
+#[test]
+fn termination_signal_custom_handler() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let (tx, rx) = oneshot::channel();
+    let signal = TerminationSignal::with_custom_handler(rx);
+    tx.send(()).unwrap();
+    assert_eq!(rt.block_on(signal), Some(()));
+}
@@ -40,7 +40,11 @@ use {
     },
 };
 
-use std::{fs::File, io, path::Path};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
+};
 
 pub struct FileLock {
     _file: File,
@@ -49,8 +53,22 @@ pub struct FileLock {
 }
 
 impl FileLock {
+    /// Read the PID stored in the lock file at the given path (without attempting to lock it). Returns `None` if the
+    /// file does not exist or doesn't hold a PID
+    pub fn read_pid<P: AsRef<Path>>(path: P) -> io::Result<Option<u32>> {
+        match fs::read_to_string(path) {
+            Ok(pid) => Ok(pid.trim().parse().ok()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
     pub fn new<P: AsRef<Path>>(path: P) -> io::Result<Self> {
-        let file = File::create(path)?;
+        // NB: don't truncate before we hold the lock, or we'll clobber the PID of the process that holds it
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
         #[cfg(windows)]
         {
             let handle = file.as_raw_handle();
@@ -65,6 +83,7 @@ impl FileLock {
                     &mut overlapped,
                 )
             }?;
+            Self::write_pid(&file)?;
             return Ok(Self {
                 _file: file,
                 handle: HANDLE(handle as isize),
@@ -83,9 +102,15 @@ impl FileLock {
                     "file is already locked",
                 ));
             }
+            Self::write_pid(&file)?;
             return Ok(Self { _file: file });
         }
     }
+    fn write_pid(mut file: &File) -> io::Result<()> {
+        file.set_len(0)?;
+        write!(file, "{}", std::process::id())?;
+        file.sync_all()
+    }
     pub fn release(self) -> io::Result<()> {
         #[cfg(windows)]
         {
@@ -25,7 +25,10 @@
 */
 
 use {
-    crate::error::{BenchError, BenchResult},
+    crate::{
+        bench::{BENCHMARK_MODEL_ID, BENCHMARK_SPACE_ID},
+        error::{BenchError, BenchResult},
+    },
     libsky::{env_vars, CliAction},
     std::{collections::hash_map::HashMap, env},
 };
@@ -41,7 +44,7 @@ enum TaskInner {
 #[derive(Debug)]
 pub enum Task {
     HelpMsg(String),
-    BenchConfig(BenchConfig),
+    BenchConfig(Box<BenchConfig>),
 }
 
 #[derive(Debug, PartialEq)]
@@ -50,6 +53,62 @@ pub enum BenchEngine {
     Fury,
 }
 
+/// An operation that can be used in a mixed workload (see `--mix`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MixOp {
+    Set,
+    Get,
+    Update,
+    Delete,
+}
+
+impl MixOp {
+    fn from_str(op: &str) -> Option<Self> {
+        Some(match op {
+            "set" => Self::Set,
+            "get" => Self::Get,
+            "upd" => Self::Update,
+            "del" => Self::Delete,
+            _ => return None,
+        })
+    }
+}
+
+/// The model that is benchmarked
+#[derive(Debug, Clone)]
+pub struct BenchTarget {
+    pub space: String,
+    pub model: String,
+    pub key_col: String,
+    pub val_col: String,
+    /// if set, the model was provided by the user and we neither create nor remove it
+    pub user_defined: bool,
+}
+
+impl BenchTarget {
+    fn user_defined(space: String, model: String, key_col: String, val_col: String) -> Self {
+        Self {
+            space,
+            model,
+            key_col,
+            val_col,
+            user_defined: true,
+        }
+    }
+}
+
+impl Default for BenchTarget {
+    fn default() -> Self {
+        Self {
+            space: BENCHMARK_SPACE_ID.into(),
+            model: BENCHMARK_MODEL_ID.into(),
+            key_col: "un".into(),
+            val_col: "pw".into(),
+            user_defined: false,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct BenchConfig {
     pub host: String,
@@ -60,34 +119,17 @@ pub struct BenchConfig {
     pub query_count: usize,
     pub engine: BenchEngine,
     pub connections: usize,
-}
-
-impl BenchConfig {
-    pub fn new(
-        host: String,
-        port: u16,
-        root_pass: String,
-        threads: usize,
-        key_size: usize,
-        query_count: usize,
-        engine: BenchEngine,
-        connections: usize,
-    ) -> Self {
-        Self {
-            host,
-            port,
-            root_pass,
-            threads,
-            key_size,
-            query_count,
-            engine,
-            connections,
-        }
-    }
+    pub json_out: Option<String>,
+    /// print the results as JSON instead of a table
+    pub json: bool,
+    pub baseline: Option<String>,
+    pub target: BenchTarget,
+    /// if set, the operations are randomly interleaved with the given percentages
+    pub mix: Option<Vec<(MixOp, u32)>>,
 }
 
 fn load_env() -> BenchResult<TaskInner> {
-    let action = libsky::parse_cli_args_disallow_duplicate()?;
+    let action = libsky::parse_cli_args_disallow_duplicate(&["--json"])?;
     match action {
         CliAction::Help => Ok(TaskInner::HelpMsg(TXT_HELP.into())),
         CliAction::Version => Ok(TaskInner::HelpMsg(libsky::version_msg("sky-bench"))),
@@ -95,6 +137,40 @@ fn load_env() -> BenchResult<TaskInner> {
     }
 }
 
+/// Parse a mix in the form `op=percent,...` (for example, `set=70,get=20,del=10`)
+fn parse_mix(mix: &str) -> BenchResult<Vec<(MixOp, u32)>> {
+    let mut ret: Vec<(MixOp, u32)> = vec![];
+    for item in mix.split(',') {
+        let Some((op, percent)) = item.trim().split_once('=') else {
+            return Err(BenchError::Config(format!(
+                "bad value for `--mix`. expected `op=percent` but got `{item}`"
+            )));
+        };
+        let Some(op) = MixOp::from_str(op.trim()) else {
+            return Err(BenchError::Config(format!(
+                "bad value for `--mix`. unknown operation `{op}`. expected one of set, get, upd or del"
+            )));
+        };
+        let Ok(percent) = percent.trim().parse::<u32>() else {
+            return Err(BenchError::Config(format!(
+                "bad value for `--mix`. `{percent}` is not a valid percentage"
+            )));
+        };
+        if ret.iter().any(|(existing, _)| *existing == op) {
+            return Err(BenchError::Config(format!(
+                "bad value for `--mix`. duplicate operation in `{item}`"
+            )));
+        }
+        ret.push((op, percent));
+    }
+    if ret.iter().map(|(_, percent)| percent).sum::<u32>() != 100 {
+        return Err(BenchError::Config(
+            "bad value for `--mix`. the percentages must add up to 100".into(),
+        ));
+    }
+    Ok(ret)
+}
+
 fn cdig(n: usize) -> usize {
     if n == 0 {
         1
@@ -115,25 +191,25 @@ pub fn parse() -> BenchResult<Task> {
             // proto@host:port
             let ep: Vec<&str> = ep.split("@").collect();
             if ep.len() != 2 {
-                return Err(BenchError::ArgsErr(
+                return Err(BenchError::Config(
                     "value for --endpoint must be in the form `[protocol]@[host]:[port]`".into(),
                 ));
             }
             let protocol = ep[0];
             let host_port: Vec<&str> = ep[1].split(":").collect();
             if host_port.len() != 2 {
-                return Err(BenchError::ArgsErr(
+                return Err(BenchError::Config(
                     "value for --endpoint must be in the form `[protocol]@[host]:[port]`".into(),
                 ));
             }
             let (host, port) = (host_port[0], host_port[1]);
             let Ok(port) = port.parse::<u16>() else {
-                return Err(BenchError::ArgsErr(
+                return Err(BenchError::Config(
                     "the value for port must be an integer in the range 0-65535".into(),
                 ));
             };
             if protocol != "tcp" {
-                return Err(BenchError::ArgsErr(
+                return Err(BenchError::Config(
                     "only TCP endpoints can be benchmarked at the moment".into(),
                 ));
             }
@@ -148,7 +224,7 @@ pub fn parse() -> BenchResult<Task> {
             match env::var(env_vars::SKYDB_PASSWORD) {
                 Ok(p) => p,
                 Err(_) => {
-                    return Err(BenchError::ArgsErr(
+                    return Err(BenchError::Config(
                         "you must provide a value for `--password`".into(),
                     ))
                 }
@@ -161,7 +237,7 @@ pub fn parse() -> BenchResult<Task> {
         Some(tc) => match tc.parse() {
             Ok(tc) if tc > 0 => tc,
             Err(_) | Ok(_) => {
-                return Err(BenchError::ArgsErr(
+                return Err(BenchError::Config(
                     "incorrect value for `--threads`. must be a nonzero value".into(),
                 ))
             }
@@ -173,18 +249,28 @@ pub fn parse() -> BenchResult<Task> {
         Some(rc) => match rc.parse() {
             Ok(rc) if rc != 0 => rc,
             Err(_) | Ok(_) => {
-                return Err(BenchError::ArgsErr(format!(
-                    "bad value for `--rowcount` must be a nonzero value"
-                )))
+                return Err(BenchError::Config(
+                    "bad value for `--rowcount` must be a nonzero value".into(),
+                ))
             }
         },
     };
-    let need_atleast = cdig(query_count);
+    let mix = match args.remove("--mix") {
+        None => None,
+        Some(mix) => Some(parse_mix(&mix)?),
+    };
+    // a mixed run inserts its `set` rows after the rows it reads and updates
+    let need_keys = if mix.is_some() {
+        query_count * 2
+    } else {
+        query_count
+    };
+    let need_atleast = cdig(need_keys);
     let key_size = match args.remove("--keysize") {
         None => need_atleast,
         Some(ks) => match ks.parse() {
             Ok(s) if s >= need_atleast => s,
-            Err(_) | Ok(_) => return Err(BenchError::ArgsErr(format!("incorrect value for `--keysize`. must be set to a value that can be used to generate atleast {query_count} unique primary keys"))),
+            Err(_) | Ok(_) => return Err(BenchError::Config(format!("incorrect value for `--keysize`. must be set to a value that can be used to generate atleast {need_keys} unique primary keys"))),
         }
     };
     let engine = match args.remove("--engine") {
@@ -196,7 +282,7 @@ pub fn parse() -> BenchResult<Task> {
             "rookie" => BenchEngine::Rookie,
             "fury" => BenchEngine::Fury,
             _ => {
-                return Err(BenchError::ArgsErr(format!(
+                return Err(BenchError::Config(format!(
                     "bad value for `--engine`. got `{engine}` but expected warp or rookie"
                 )))
             }
@@ -207,31 +293,61 @@ pub fn parse() -> BenchResult<Task> {
         Some(c) => match c.parse::<usize>() {
             Ok(s) if s != 0 => {
                 if engine == BenchEngine::Rookie {
-                    return Err(BenchError::ArgsErr(format!(
-                        "the 'rookie' engine does not support explicit connection count. the number of threads is the connection count"
-                    )));
+                    return Err(BenchError::Config("the 'rookie' engine does not support explicit connection count. the number of threads is the connection count".into()));
                 }
                 s
             }
             _ => {
-                return Err(BenchError::ArgsErr(format!(
-                    "bad value for `--connections`. must be a nonzero value"
-                )))
+                return Err(BenchError::Config(
+                    "bad value for `--connections`. must be a nonzero value".into(),
+                ))
             }
         },
     };
+    let json_out = args.remove("--json-out");
+    let json = match args.remove("--json").as_deref() {
+        None | Some("false") => false,
+        Some("true") => true,
+        Some(json) => {
+            return Err(BenchError::Config(format!(
+                "bad value for `--json`. got `{json}` but expected true or false"
+            )))
+        }
+    };
+    let baseline = args.remove("--baseline");
+    let target = match (
+        args.remove("--space"),
+        args.remove("--model"),
+        args.remove("--key-col"),
+        args.remove("--val-col"),
+    ) {
+        (None, None, None, None) => BenchTarget::default(),
+        (Some(space), Some(model), Some(key_col), Some(val_col)) => {
+            BenchTarget::user_defined(space, model, key_col, val_col)
+        }
+        _ => {
+            return Err(BenchError::Config(
+                "`--space`, `--model`, `--key-col` and `--val-col` must be used together".into(),
+            ))
+        }
+    };
     if args.is_empty() {
-        Ok(Task::BenchConfig(BenchConfig::new(
+        Ok(Task::BenchConfig(Box::new(BenchConfig {
             host,
             port,
-            passsword,
-            thread_count,
+            root_pass: passsword,
+            threads: thread_count,
             key_size,
             query_count,
             engine,
             connections,
-        )))
+            json_out,
+            json,
+            baseline,
+            target,
+            mix,
+        })))
     } else {
-        Err(BenchError::ArgsErr(format!("unrecognized arguments")))
+        Err(BenchError::Config("unrecognized arguments".into()))
     }
 }
@@ -26,20 +26,30 @@
 
 use skytable::response::Value;
 
-use crate::args::BenchEngine;
+use crate::args::{BenchEngine, BenchTarget, MixOp};
 
 use {
     crate::{
         args::BenchConfig,
-        error::{self, BenchResult},
+        error::{self, BenchError, BenchResult},
         runtime::{fury, rookie, RuntimeStats},
     },
+    rand::{
+        distributions::{Distribution, WeightedIndex},
+        Rng,
+    },
     skytable::{error::Error, query, response::Response, Config, Connection, Query},
-    std::{fmt, time::Instant},
+    std::{
+        fmt, fs, mem,
+        sync::Arc,
+        time::{Duration, Instant},
+    },
 };
 
 pub const BENCHMARK_SPACE_ID: &'static str = "bench";
 pub const BENCHMARK_MODEL_ID: &'static str = "bench";
+/// how long we wait for all the fury workers to connect
+const FURY_INIT_TIMEOUT: Duration = Duration::from_secs(60);
 
 /*
     task impl
@@ -50,11 +60,12 @@ pub const BENCHMARK_MODEL_ID: &'static str = "bench";
 #[derive(Debug)]
 pub struct BombardTask {
     config: Config,
+    space: String,
 }
 
 impl BombardTask {
-    pub fn new(config: Config) -> Self {
-        Self { config }
+    pub fn new(config: Config, space: String) -> Self {
+        Self { config, space }
     }
 }
 
@@ -82,27 +93,32 @@ impl From<Error> for BombardTaskError {
 
 impl rookie::ThreadedBombardTask for BombardTask {
     type Worker = Connection;
-    type WorkerTask = (Query, (BenchmarkTask, u64));
-    type WorkerTaskSpec = BenchmarkTask;
+    type WorkerTask = (Query, (usize, BenchmarkTask, u64));
+    type WorkerTaskSpec = Workload;
     type WorkerInitError = Error;
     type WorkerTaskError = BombardTaskError;
     fn worker_init(&self) -> Result<Self::Worker, Self::WorkerInitError> {
         let mut db = self.config.connect()?;
-        db.query_parse::<()>(&skytable::query!(format!("use {BENCHMARK_SPACE_ID}")))
+        db.query_parse::<()>(&skytable::query!(format!("use {}", self.space)))
             .map(|_| db)
     }
+    fn op_count(spec: &Self::WorkerTaskSpec) -> usize {
+        spec.op_count()
+    }
     fn generate_task(spec: &Self::WorkerTaskSpec, current: u64) -> Self::WorkerTask {
-        (spec.generate_query(current), (*spec, current))
+        let op = spec.choose(&mut rand::thread_rng());
+        let task = spec.op(op).clone();
+        (task.generate_query(current), (op, task, current))
     }
     fn worker_drive_timed(
         worker: &mut Self::Worker,
-        (query, (spec, current)): Self::WorkerTask,
-    ) -> Result<u128, Self::WorkerTaskError> {
+        (query, (op, spec, current)): Self::WorkerTask,
+    ) -> Result<(usize, u128), Self::WorkerTaskError> {
         let start = Instant::now();
         let ret = worker.query(&query)?;
         let stop = Instant::now();
         if spec.verify_response(current, ret) {
-            Ok(stop.duration_since(start).as_nanos())
+            Ok((op, stop.duration_since(start).as_nanos()))
         } else {
             Err(BombardTaskError::Mismatch)
         }
@@ -114,18 +130,32 @@ impl rookie::ThreadedBombardTask for BombardTask {
 */
 
 pub fn run(bench: BenchConfig) -> error::BenchResult<()> {
-    let bench_config = BombardTask::new(Config::new(
-        &bench.host,
-        bench.port,
-        "root",
-        &bench.root_pass,
-    ));
-    info!("running preliminary checks and creating model `bench.bench` with definition: `{{un: binary, pw: uint8}}`");
+    let target = bench.target.clone();
+    let bench_config = BombardTask::new(
+        Config::new(&bench.host, bench.port, "root", &bench.root_pass),
+        target.space.clone(),
+    );
     let mut main_thread_db = bench_config.config.connect()?;
-    main_thread_db.query_parse::<()>(&query!("create space bench"))?;
-    main_thread_db.query_parse::<()>(&query!(format!(
-        "create model {BENCHMARK_SPACE_ID}.{BENCHMARK_MODEL_ID}(un: binary, pw: uint8)"
-    )))?;
+    if target.user_defined {
+        info!(
+            "running preliminary checks for user-defined model `{}.{}` with key `{}` and value `{}`",
+            target.space, target.model, target.key_col, target.val_col
+        );
+        main_thread_db.query_parse::<String>(&query!(format!(
+            "inspect model {}.{}",
+            target.space, target.model
+        )))?;
+    } else {
+        info!("running preliminary checks and creating model `bench.bench` with definition: `{{un: binary, pw: uint8}}`");
+        main_thread_db.query_parse::<()>(&query!("create space bench"))?;
+        main_thread_db.query_parse::<()>(&query!(format!(
+            "create model {BENCHMARK_SPACE_ID}.{BENCHMARK_MODEL_ID}(un: binary, pw: uint8)"
+        )))?;
+    }
+    let json_out = bench.json_out.clone();
+    let json = bench.json;
+    let baseline = bench.baseline.clone();
+    let bench_mix = bench.mix.is_some();
     let stats = match bench.engine {
         BenchEngine::Rookie => bench_rookie(bench_config, bench),
         BenchEngine::Fury => bench_fury(bench),
@@ -134,37 +164,187 @@ pub fn run(bench: BenchConfig) -> error::BenchResult<()> {
         Ok(ret) => ret,
         Err(e) => {
             error!("benchmarking failed. attempting to clean up");
-            match cleanup(main_thread_db) {
+            if target.user_defined {
+                warn!(
+                    "rows inserted by the benchmark might be left behind in `{}.{}`",
+                    target.space, target.model
+                );
+            }
+            match cleanup(main_thread_db, &target) {
                 Ok(()) => return Err(e),
                 Err(e_cleanup) => {
-                    error!("failed to clean up db: {e_cleanup}. please remove model `bench.bench` manually");
+                    error!(
+                        "failed to clean up db: {e_cleanup}. please remove model `{}.{}` manually",
+                        target.space, target.model
+                    );
                     return Err(e);
                 }
             }
         }
     };
+    if target.user_defined && bench_mix {
+        warn!(
+            "rows left behind by the mixed workload were not removed from `{}.{}`",
+            target.space, target.model
+        );
+    }
     info!(
         "{} queries executed. benchmark complete.",
         fmt_u64(total_queries)
     );
     warn!("benchmarks might appear to be slower. this tool is currently experimental");
     // print results
-    print_table(stats);
-    cleanup(main_thread_db)?;
+    let report = BenchReport::new(stats);
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        print_table(&report);
+    }
+    cleanup(main_thread_db, &target)?;
+    if let Some(json_out) = json_out {
+        fs::write(&json_out, report.to_json()).map_err(|e| {
+            error!("failed to write results to `{json_out}`");
+            BenchError::Io(e)
+        })?;
+        info!("results written to `{json_out}`");
+    }
+    if let Some(baseline) = baseline {
+        let regressions = report.compare_baseline(&baseline)?;
+        if !regressions.is_empty() {
+            for regression in &regressions {
+                error!("{regression}");
+            }
+            return Err(BenchError::Regressed(regressions.len()));
+        }
+        info!("no regressions against baseline `{baseline}`");
+    }
     Ok(())
 }
 
+/*
+    report
+*/
+
+/// the drop in QPS (in percent) beyond which an operation is considered to have regressed
+const REGRESSION_THRESHOLD_PCT: f64 = 5.0;
+
+/// The results of a benchmark run
+pub struct BenchReport {
+    stats: Vec<(&'static str, RuntimeStats)>,
+}
+
+/// An operation whose throughput dropped below the baseline
+#[derive(Debug)]
+pub struct Regression {
+    pub operation: String,
+    pub expected_qps: f64,
+    pub actual_qps: f64,
+    pub delta_pct: f64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` regressed by {:.2}% (baseline: {:.2} qps, now: {:.2} qps)",
+            self.operation, -self.delta_pct, self.expected_qps, self.actual_qps
+        )
+    }
+}
+
+impl BenchReport {
+    fn new(stats: Vec<(&'static str, RuntimeStats)>) -> Self {
+        Self { stats }
+    }
+    /// Returns the results as a JSON object, keyed by the operation
+    pub fn to_json(&self) -> String {
+        let stats: Vec<String> = self
+            .stats
+            .iter()
+            .map(|(query, stats)| {
+                // JSON has no representation for NaN or infinity
+                let qps = if stats.qps.is_finite() {
+                    stats.qps.to_string()
+                } else {
+                    "null".to_owned()
+                };
+                format!(
+                    "\"{query}\":{{\"qps\":{qps},\"head\":{},\"tail\":{}}}",
+                    stats.head, stats.tail
+                )
+            })
+            .collect();
+        format!("{{{}}}", stats.join(","))
+    }
+    /// Compare the results against the JSON output of a previous run and return all operations whose QPS dropped by
+    /// more than [`REGRESSION_THRESHOLD_PCT`]. Operations missing from the baseline are ignored
+    pub fn compare_baseline(&self, baseline_path: &str) -> Result<Vec<Regression>, BenchError> {
+        let baseline = fs::read_to_string(baseline_path).map_err(|e| {
+            error!("failed to read baseline `{baseline_path}`");
+            BenchError::Io(e)
+        })?;
+        let baseline = parse_baseline(&baseline).ok_or_else(|| {
+            BenchError::ReportError(format!("baseline `{baseline_path}` is malformed"))
+        })?;
+        Ok(self.regressions(&baseline))
+    }
+    fn regressions(&self, baseline: &[(String, f64)]) -> Vec<Regression> {
+        let mut regressions = vec![];
+        for (query, RuntimeStats { qps, .. }) in &self.stats {
+            let Some((_, expected_qps)) = baseline.iter().find(|(op, _)| op == query) else {
+                continue;
+            };
+            let delta_pct = (qps - expected_qps) / expected_qps * 100.0;
+            if delta_pct < -REGRESSION_THRESHOLD_PCT {
+                regressions.push(Regression {
+                    operation: query.to_string(),
+                    expected_qps: *expected_qps,
+                    actual_qps: *qps,
+                    delta_pct,
+                });
+            }
+        }
+        regressions
+    }
+}
+
+/// Parse the QPS for each operation from the output of [`BenchReport::to_json`]. Every QPS must be finite and
+/// positive since we compute the relative change against it
+fn parse_baseline(json: &str) -> Option<Vec<(String, f64)>> {
+    let body = json.trim().strip_prefix('{')?.strip_suffix('}')?;
+    let mut ret = vec![];
+    for entry in body.split_terminator("},") {
+        let (query, fields) = entry.trim().trim_end_matches('}').split_once(":{")?;
+        let query = query.trim().strip_prefix('"')?.strip_suffix('"')?;
+        let qps: f64 = fields
+            .split(',')
+            .find_map(|field| field.trim().strip_prefix("\"qps\":"))?
+            .trim()
+            .parse()
+            .ok()?;
+        if !(qps.is_finite() && qps > 0.0) {
+            return None;
+        }
+        ret.push((query.to_owned(), qps));
+    }
+    Some(ret)
+}
+
 /*
     util
 */
 
-fn cleanup(mut main_thread_db: Connection) -> Result<(), error::BenchError> {
+fn cleanup(mut main_thread_db: Connection, target: &BenchTarget) -> Result<(), error::BenchError> {
+    if target.user_defined {
+        // we don't own this model. the rows we inserted are removed by the `DELETE` benchmark
+        return Ok(());
+    }
     trace!("dropping space and table");
     main_thread_db.query_parse::<()>(&query!("drop space allow not empty bench"))?;
     Ok(())
 }
 
-fn print_table(data: Vec<(&'static str, RuntimeStats)>) {
+fn print_table(report: &BenchReport) {
     println!(
         "+---------+--------------------------+-----------------------+------------------------+"
     );
@@ -174,10 +354,10 @@ fn print_table(data: Vec<(&'static str, RuntimeStats)>) {
     println!(
         "+---------+--------------------------+-----------------------+------------------------+"
     );
-    for (query, RuntimeStats { qps, head, tail }) in data {
+    for (query, stats) in &report.stats {
         println!(
             "| {:<7} | {:>24.2} | {:>21} | {:>22} |",
-            query, qps, tail, head
+            query, stats.qps, stats.tail, stats.head
         );
     }
     println!(
@@ -189,16 +369,19 @@ fn print_table(data: Vec<(&'static str, RuntimeStats)>) {
     bench runner
 */
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct BenchmarkTask {
     gen_query: fn(&Self, u64) -> Query,
     check_resp: fn(&Self, u64, Response) -> bool,
     pk_len: usize,
+    query: Arc<str>,
+    key_offset: u64,
 }
 
 impl BenchmarkTask {
     fn new(
         pk_len: usize,
+        query: String,
         gen_query: fn(&Self, u64) -> Query,
         check_resp: fn(&Self, u64, Response) -> bool,
     ) -> Self {
@@ -206,10 +389,21 @@ impl BenchmarkTask {
             gen_query,
             check_resp,
             pk_len,
+            query: query.into(),
+            key_offset: 0,
         }
     }
+    /// Use the keys after `offset` (so that this task doesn't touch rows that were already inserted)
+    fn with_key_offset(self, key_offset: u64) -> Self {
+        Self { key_offset, ..self }
+    }
     fn fmt_pk(&self, current: u64) -> Vec<u8> {
-        format!("{:0>width$}", current, width = self.pk_len).into_bytes()
+        format!(
+            "{:0>width$}",
+            current + self.key_offset,
+            width = self.pk_len
+        )
+        .into_bytes()
     }
     pub fn generate_query(&self, current: u64) -> Query {
         (self.gen_query)(self, current)
@@ -219,21 +413,87 @@ impl BenchmarkTask {
     }
 }
 
+/// The operations run by a benchmark. Every query runs one of them, picked at random according to the weights (if
+/// there's more than one)
+#[derive(Clone, Debug)]
+pub struct Workload {
+    ops: Vec<BenchmarkTask>,
+    weights: Option<WeightedIndex<u32>>,
+}
+
+impl Workload {
+    fn single(task: BenchmarkTask) -> Self {
+        Self {
+            ops: vec![task],
+            weights: None,
+        }
+    }
+    fn mixed(ops: Vec<(BenchmarkTask, u32)>) -> Self {
+        let weights = WeightedIndex::new(ops.iter().map(|(_, weight)| *weight))
+            .expect("the weights are validated while parsing args");
+        Self {
+            ops: ops.into_iter().map(|(task, _)| task).collect(),
+            weights: Some(weights),
+        }
+    }
+    /// Pick the next operation
+    pub fn choose(&self, rng: &mut impl Rng) -> usize {
+        match &self.weights {
+            Some(weights) => weights.sample(rng),
+            None => 0,
+        }
+    }
+    pub fn op(&self, op: usize) -> &BenchmarkTask {
+        &self.ops[op]
+    }
+    /// The number of operations in the workload
+    pub fn op_count(&self) -> usize {
+        self.ops.len()
+    }
+}
+
 struct BenchItem {
     name: &'static str,
-    spec: BenchmarkTask,
+    spec: Workload,
     count: usize,
+    /// the names of the operations in a mixed workload
+    breakdown: Vec<&'static str>,
 }
 
 impl BenchItem {
     fn new(name: &'static str, spec: BenchmarkTask, count: usize) -> Self {
-        Self { name, spec, count }
+        Self {
+            name,
+            spec: Workload::single(spec),
+            count,
+            breakdown: vec![],
+        }
+    }
+    fn new_mixed(
+        name: &'static str,
+        ops: Vec<(&'static str, BenchmarkTask, u32)>,
+        count: usize,
+    ) -> Self {
+        Self {
+            name,
+            breakdown: ops.iter().map(|(name, _, _)| *name).collect(),
+            spec: Workload::mixed(
+                ops.into_iter()
+                    .map(|(_, task, weight)| (task, weight))
+                    .collect(),
+            ),
+            count,
+        }
     }
     fn print_log_start(&self) {
         info!(
             "benchmarking `{}`. average payload size = {} bytes. queries = {}",
             self.name,
-            self.spec.generate_query(0).debug_encode_packet().len(),
+            self.spec
+                .op(0)
+                .generate_query(0)
+                .debug_encode_packet()
+                .len(),
             self.count
         )
     }
@@ -248,55 +508,97 @@ impl BenchItem {
     }
 }
 
+/// Add the stats for a benchmark to the results. For mixed workloads, the stats for each operation are added before
+/// the aggregate
+fn push_results(
+    results: &mut Vec<(&'static str, RuntimeStats)>,
+    name: &'static str,
+    breakdown: Vec<&'static str>,
+    mut stats: RuntimeStats,
+) {
+    if !breakdown.is_empty() {
+        results.extend(breakdown.into_iter().zip(mem::take(&mut stats.breakdown)));
+    }
+    results.push((name, stats));
+}
+
 fn prepare_bench_spec(bench: &BenchConfig) -> Vec<BenchItem> {
-    vec![
-        BenchItem::new(
-            "INSERT",
-            BenchmarkTask::new(
-                bench.key_size,
-                |me, current| query!("insert into bench(?, ?)", me.fmt_pk(current), 0u64),
-                |_, _, actual_resp| actual_resp == Response::Empty,
-            ),
-            bench.query_count,
-        ),
-        BenchItem::new(
-            "SELECT",
-            BenchmarkTask::new(
-                bench.key_size,
-                |me, current| query!("select * from bench where un = ?", me.fmt_pk(current)),
-                |me, current, resp| match resp {
-                    Response::Row(r) => {
-                        r.into_values() == vec![Value::Binary(me.fmt_pk(current)), Value::UInt8(0)]
-                    }
-                    _ => false,
-                },
-            ),
-            bench.query_count,
-        ),
-        BenchItem::new(
-            "UPDATE",
-            BenchmarkTask::new(
-                bench.key_size,
-                |me, current| {
-                    query!(
-                        "update bench set pw += ? where un = ?",
-                        1u64,
-                        me.fmt_pk(current)
-                    )
-                },
-                |_, _, resp| resp == Response::Empty,
-            ),
-            bench.query_count,
-        ),
-        BenchItem::new(
-            "DELETE",
-            BenchmarkTask::new(
-                bench.key_size,
-                |me, current| query!("delete from bench where un = ?", me.fmt_pk(current)),
-                |_, _, resp| resp == Response::Empty,
+    let BenchTarget {
+        model,
+        key_col,
+        val_col,
+        user_defined,
+        ..
+    } = &bench.target;
+    let (insert, select) = if *user_defined {
+        // the user's model might have more fields (or a different field order), so be explicit
+        (
+            format!("insert into {model} {{ {key_col}: ?, {val_col}: ? }}"),
+            format!("select {key_col}, {val_col} from {model} where {key_col} = ?"),
+        )
+    } else {
+        (
+            format!("insert into {model}(?, ?)"),
+            format!("select * from {model} where {key_col} = ?"),
+        )
+    };
+    let insert = BenchmarkTask::new(
+        bench.key_size,
+        insert,
+        |me, current| query!(&*me.query, me.fmt_pk(current), 0u64),
+        |_, _, actual_resp| actual_resp == Response::Empty,
+    );
+    let select = BenchmarkTask::new(
+        bench.key_size,
+        select,
+        |me, current| query!(&*me.query, me.fmt_pk(current)),
+        |me, current, resp| match resp {
+            Response::Row(r) => {
+                r.into_values() == vec![Value::Binary(me.fmt_pk(current)), Value::UInt8(0)]
+            }
+            _ => false,
+        },
+    );
+    let update = BenchmarkTask::new(
+        bench.key_size,
+        format!("update {model} set {val_col} += ? where {key_col} = ?"),
+        |me, current| query!(&*me.query, 1u64, me.fmt_pk(current)),
+        |_, _, resp| resp == Response::Empty,
+    );
+    let delete = BenchmarkTask::new(
+        bench.key_size,
+        format!("delete from {model} where {key_col} = ?"),
+        |me, current| query!(&*me.query, me.fmt_pk(current)),
+        |_, _, resp| resp == Response::Empty,
+    );
+    let Some(mix) = &bench.mix else {
+        return vec![
+            BenchItem::new("INSERT", insert, bench.query_count),
+            BenchItem::new("SELECT", select, bench.query_count),
+            BenchItem::new("UPDATE", update, bench.query_count),
+            BenchItem::new("DELETE", delete, bench.query_count),
+        ];
+    };
+    /*
+        every key is used by exactly one query in the mix, so we first insert all the rows that are read, updated or
+        deleted. `set` inserts new rows (after the ones we inserted) so that it doesn't conflict with them
+    */
+    let ops = mix
+        .iter()
+        .map(|(op, percent)| match op {
+            MixOp::Set => (
+                "MIX-SET",
+                insert.clone().with_key_offset(bench.query_count as u64),
+                *percent,
             ),
-            bench.query_count,
-        ),
+            MixOp::Get => ("MIX-GET", select.clone(), *percent),
+            MixOp::Update => ("MIX-UPD", update.clone(), *percent),
+            MixOp::Delete => ("MIX-DEL", delete.clone(), *percent),
+        })
+        .collect();
+    vec![
+        BenchItem::new("INSERT", insert, bench.query_count),
+        BenchItem::new_mixed("MIXED", ops, bench.query_count),
     ]
 }
 
@@ -326,13 +628,13 @@ fn bench_rookie(
     // prepare benches
     let benches = prepare_bench_spec(&bench);
     // bench
-    let total_queries = bench.query_count as u64 * benches.len() as u64;
+    let total_queries = benches.iter().map(|task| task.count as u64).sum();
     let mut results = vec![];
-    for task in benches {
-        let name = task.name;
+    for mut task in benches {
+        let (name, breakdown) = (task.name, mem::take(&mut task.breakdown));
         task.print_log_start();
         let this_result = task.run(&mut pool)?;
-        results.push((name, this_result));
+        push_results(&mut results, name, breakdown, this_result);
     }
     Ok((total_queries, results))
 }
@@ -348,22 +650,86 @@ fn bench_fury(bench: BenchConfig) -> BenchResult<(u64, Vec<(&'static str, Runtim
             "initializing connections. engine=fury, threads={}, connections={}, primary key size ={} bytes",
             bench.threads, bench.connections, bench.key_size
         );
-        let mut pool = fury::Fury::new(
-            bench.connections,
-            Config::new(&bench.host, bench.port, "root", &bench.root_pass),
+        let mut pool = tokio::time::timeout(
+            FURY_INIT_TIMEOUT,
+            fury::Fury::new(
+                bench.connections,
+                Config::new(&bench.host, bench.port, "root", &bench.root_pass),
+                &bench.target.space,
+            ),
         )
-        .await?;
+        .await
+        .map_err(|_| BenchError::Timeout)??;
         // prepare benches
         let benches = prepare_bench_spec(&bench);
         // bench
-        let total_queries = bench.query_count as u64 * benches.len() as u64;
+        let total_queries = benches.iter().map(|task| task.count as u64).sum();
         let mut results = vec![];
-        for task in benches {
-            let name = task.name;
+        for mut task in benches {
+            let (name, breakdown) = (task.name, mem::take(&mut task.breakdown));
             task.print_log_start();
             let this_result = task.run_async(&mut pool).await?;
-            results.push((name, this_result));
+            push_results(&mut results, name, breakdown, this_result);
         }
         Ok((total_queries,results))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_baseline, BenchReport, RuntimeStats};
+
+    fn stats(qps: f64) -> RuntimeStats {
+        RuntimeStats {
+            qps,
+            head: 10,
+            tail: 20,
+            breakdown: vec![],
+        }
+    }
+
+    #[test]
+    fn baseline_roundtrip() {
+        let report = BenchReport::new(vec![("INSERT", stats(1000.5)), ("SELECT", stats(2000.0))]);
+        let baseline = parse_baseline(&report.to_json()).unwrap();
+        assert_eq!(
+            baseline,
+            vec![("INSERT".to_owned(), 1000.5), ("SELECT".to_owned(), 2000.0)]
+        );
+        assert!(report.regressions(&baseline).is_empty());
+        let now = BenchReport::new(vec![("INSERT", stats(500.25)), ("SELECT", stats(1990.0))]);
+        let regressions = now.regressions(&baseline);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].operation, "INSERT");
+        assert_eq!(regressions[0].delta_pct, -50.0);
+    }
+
+    #[test]
+    fn baseline_ignores_missing_operations() {
+        let baseline =
+            parse_baseline(&BenchReport::new(vec![("INSERT", stats(100.0))]).to_json()).unwrap();
+        let now = BenchReport::new(vec![("INSERT", stats(100.0)), ("DELETE", stats(1.0))]);
+        assert!(now.regressions(&baseline).is_empty());
+    }
+
+    #[test]
+    fn non_finite_qps_is_valid_json() {
+        let report = BenchReport::new(vec![
+            ("INSERT", stats(f64::INFINITY)),
+            ("SELECT", stats(f64::NAN)),
+        ]);
+        assert_eq!(
+            report.to_json(),
+            "{\"INSERT\":{\"qps\":null,\"head\":10,\"tail\":20},\"SELECT\":{\"qps\":null,\"head\":10,\"tail\":20}}"
+        );
+        assert!(parse_baseline(&report.to_json()).is_none());
+    }
+
+    #[test]
+    fn baseline_rejects_invalid_qps() {
+        for qps in ["0", "0.0", "-10", "inf", "NaN", "null"] {
+            let json = format!("{{\"INSERT\":{{\"qps\":{qps},\"head\":1,\"tail\":1}}}}");
+            assert!(parse_baseline(&json).is_none(), "accepted qps={qps}");
+        }
+    }
+}
@@ -31,16 +31,25 @@ use {
     },
     core::fmt,
     skytable::error::Error,
+    std::io,
 };
 
 pub type BenchResult<T> = Result<T, BenchError>;
 
 #[derive(Debug)]
 pub enum BenchError {
-    ArgsErr(String),
+    /// bad arguments or configuration
+    Config(String),
     RookieEngineError(BombardError<BombardTask>),
     FuryEngineError(fury::FuryError),
-    DirectDbError(Error),
+    /// a direct operation (from the main thread) on the database failed
+    Connection(Error),
+    /// reading or writing a report failed
+    Io(io::Error),
+    /// the workers didn't come online in time
+    Timeout,
+    ReportError(String),
+    Regressed(usize),
 }
 
 impl From<fury::FuryError> for BenchError {
@@ -53,10 +62,10 @@ impl From<libsky::ArgParseError> for BenchError {
     fn from(e: libsky::ArgParseError) -> Self {
         match e {
             libsky::ArgParseError::Duplicate(d) => {
-                Self::ArgsErr(format!("duplicate value for `{d}`"))
+                Self::Config(format!("duplicate value for `{d}`"))
             }
             libsky::ArgParseError::MissingValue(m) => {
-                Self::ArgsErr(format!("missing value for `{m}`"))
+                Self::Config(format!("missing value for `{m}`"))
             }
         }
     }
@@ -64,7 +73,13 @@ impl From<libsky::ArgParseError> for BenchError {
 
 impl From<Error> for BenchError {
     fn from(e: Error) -> Self {
-        Self::DirectDbError(e)
+        Self::Connection(e)
+    }
+}
+
+impl From<io::Error> for BenchError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
     }
 }
 
@@ -77,15 +92,33 @@ impl From<BombardError<BombardTask>> for BenchError {
 impl fmt::Display for BenchError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::ArgsErr(e) => write!(f, "args error: {e}"),
-            Self::DirectDbError(e) => write!(f, "direct operation on db failed. {e}"),
+            Self::Config(e) => write!(f, "config error: {e}"),
+            // the cause is reported through `source`
+            Self::Connection(_) => write!(f, "direct operation on db failed"),
+            Self::Io(_) => write!(f, "I/O error"),
+            Self::Timeout => write!(f, "timed out while waiting for workers to come online"),
             Self::RookieEngineError(e) => write!(f, "benchmark failed (rookie engine): {e}"),
-            Self::FuryEngineError(e) => write!(f, "benchmark failed (fury engine): {e}"),
+            Self::FuryEngineError(_) => write!(f, "benchmark failed (fury engine)"),
+            Self::ReportError(e) => write!(f, "report error: {e}"),
+            Self::Regressed(n) => write!(f, "{n} operation(s) regressed against the baseline"),
         }
     }
 }
 
-impl std::error::Error for BenchError {}
+impl std::error::Error for BenchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Connection(e) => Some(e),
+            Self::Io(e) => Some(e),
+            Self::FuryEngineError(e) => Some(e),
+            Self::Config(_)
+            | Self::RookieEngineError(_)
+            | Self::Timeout
+            | Self::ReportError(_)
+            | Self::Regressed(_) => None,
+        }
+    }
+}
 
 #[derive(Debug)]
 pub enum BenchmarkTaskWorkerError {
@@ -39,6 +39,11 @@ fn main() {
         Ok(()) => {}
         Err(e) => {
             error!("bench error: {e}");
+            let mut source = std::error::Error::source(&e);
+            while let Some(cause) = source {
+                error!("caused by: {cause}");
+                source = cause.source();
+            }
             std::process::exit(0x01);
         }
     }
@@ -48,7 +53,7 @@ fn run() -> error::BenchResult<()> {
     let task = args::parse()?;
     match task {
         args::Task::HelpMsg(msg) => println!("{msg}"),
-        args::Task::BenchConfig(bench) => bench::run(bench)?,
+        args::Task::BenchConfig(bench) => bench::run(*bench)?,
     }
     Ok(())
 }
@@ -47,6 +47,8 @@ pub struct RuntimeStats {
     pub qps: f64,
     pub head: u128,
     pub tail: u128,
+    /// the stats for each operation kind in the task (indexed by the operation)
+    pub breakdown: Vec<RuntimeStats>,
 }
 
 #[derive(Debug)]
@@ -55,15 +57,73 @@ struct WorkerLocalStats {
     elapsed: u128,
     head: u128,
     tail: u128,
+    ops: Vec<OpStats>,
 }
 
 impl WorkerLocalStats {
-    fn new(start: Instant, elapsed: u128, head: u128, tail: u128) -> Self {
+    fn new(start: Instant, elapsed: u128, head: u128, tail: u128, ops: Vec<OpStats>) -> Self {
         Self {
             start,
             elapsed,
             head,
             tail,
+            ops,
         }
     }
 }
+
+/// Stats for a single operation kind
+#[derive(Debug, Clone, Copy)]
+struct OpStats {
+    count: usize,
+    head: u128,
+    tail: u128,
+}
+
+impl OpStats {
+    const fn new() -> Self {
+        Self {
+            count: 0,
+            head: u128::MAX,
+            tail: 0,
+        }
+    }
+    fn merge(&mut self, other: &Self) {
+        self.count += other.count;
+        self.head = self.head.min(other.head);
+        self.tail = self.tail.max(other.tail);
+    }
+}
+
+fn ops_record(ops: &mut Vec<OpStats>, op: usize, elapsed: u128) {
+    if ops.len() <= op {
+        ops.resize(op + 1, OpStats::new());
+    }
+    ops[op].merge(&OpStats {
+        count: 1,
+        head: elapsed,
+        tail: elapsed,
+    });
+}
+
+fn ops_merge(global: &mut Vec<OpStats>, local: &[OpStats]) {
+    if global.len() < local.len() {
+        global.resize(local.len(), OpStats::new());
+    }
+    global
+        .iter_mut()
+        .zip(local)
+        .for_each(|(global, local)| global.merge(local));
+}
+
+/// The throughput of each operation kind over the entire run (which is why the individual QPS add up to the total)
+fn ops_breakdown(ops: &[OpStats], time_taken_in_nanos: u128) -> Vec<RuntimeStats> {
+    ops.iter()
+        .map(|op| RuntimeStats {
+            qps: qps(op.count, time_taken_in_nanos),
+            head: if op.count == 0 { 0 } else { op.head },
+            tail: op.tail,
+            breakdown: vec![],
+        })
+        .collect()
+}
@@ -25,8 +25,9 @@
 */
 
 use {
-    super::{RuntimeStats, WorkerLocalStats, WorkerTask},
-    crate::bench::{BenchmarkTask, BENCHMARK_SPACE_ID},
+    super::{OpStats, RuntimeStats, WorkerLocalStats, WorkerTask},
+    crate::bench::Workload,
+    rand::{rngs::StdRng, SeedableRng},
     skytable::Config,
     std::{
         fmt,
@@ -87,13 +88,22 @@ pub enum FuryError {
 impl fmt::Display for FuryError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Init(e) => write!(f, "fury init failed. {e}"),
+            Self::Init(_) => write!(f, "fury init failed"),
             Self::Worker(e) => write!(f, "worker failed. {e}"),
             Self::Dead => write!(f, "all workers offline"),
         }
     }
 }
 
+impl std::error::Error for FuryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Init(e) | Self::Worker(FuryWorkerError::DbError(e)) => Some(e),
+            Self::Worker(FuryWorkerError::Mismatch) | Self::Dead => None,
+        }
+    }
+}
+
 impl From<FuryWorkerError> for FuryError {
     fn from(e: FuryWorkerError) -> Self {
         Self::Worker(e)
@@ -109,7 +119,7 @@ pub enum FuryWorkerError {
 impl fmt::Display for FuryWorkerError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::DbError(e) => write!(f, "client errored. {e}"),
+            Self::DbError(_) => write!(f, "client errored"),
             Self::Mismatch => write!(f, "server response did not match expected response"),
         }
     }
@@ -121,13 +131,13 @@ impl fmt::Display for FuryWorkerError {
 
 #[derive(Debug)]
 pub struct Fury {
-    tx_task: broadcast::Sender<WorkerTask<BenchmarkTask>>,
+    tx_task: broadcast::Sender<WorkerTask<Workload>>,
     rx_task_result: mpsc::Receiver<FuryResult<WorkerLocalStats>>,
     client_count: usize,
 }
 
 impl Fury {
-    pub async fn new(client_count: usize, config: Config) -> FuryResult<Self> {
+    pub async fn new(client_count: usize, config: Config, space: &str) -> FuryResult<Self> {
         let (tx_task, rx_task) = broadcast::channel(1);
         let (tx_task_result, rx_task_result) = mpsc::channel(client_count);
         let (tx_ack, mut rx_ack) = mpsc::channel(1);
@@ -136,9 +146,10 @@ impl Fury {
             let tx_task_result = tx_task_result.clone();
             let tx_ack = tx_ack.clone();
             let config = config.clone();
-            tokio::spawn(
-                async move { worker_svc(id, rx_task, tx_task_result, tx_ack, config).await },
-            );
+            let space = space.to_owned();
+            tokio::spawn(async move {
+                worker_svc(id, rx_task, tx_task_result, tx_ack, config, space).await
+            });
         }
         drop((tx_ack, rx_task));
         match rx_ack.recv().await {
@@ -152,10 +163,12 @@ impl Fury {
             client_count,
         })
     }
-    pub async fn bombard(&mut self, count: usize, task: BenchmarkTask) -> FuryResult<RuntimeStats> {
+    pub async fn bombard(&mut self, count: usize, task: Workload) -> FuryResult<RuntimeStats> {
         // pause workers and set target
         let start_guard = GLOBAL_START.write().await;
         gset_target(count);
+        // an op that's never picked still gets (empty) stats
+        let mut global_ops = vec![OpStats::new(); task.op_count()];
         // send tasks
         if self.tx_task.send(WorkerTask::Task(task)).is_err() {
             return Err(FuryError::Dead);
@@ -174,6 +187,7 @@ impl Fury {
                 elapsed: this_elapsed,
                 head: this_head,
                 tail: this_tail,
+                ops: this_ops,
             } = match self.rx_task_result.recv().await {
                 None => {
                     return Err(FuryError::Dead);
@@ -203,28 +217,29 @@ impl Fury {
             if this_tail > global_tail {
                 global_tail = this_tail;
             }
+            super::ops_merge(&mut global_ops, &this_ops);
             remaining -= 1;
         }
+        let global_elapsed = global_stop
+            .unwrap()
+            .duration_since(global_start.unwrap())
+            .as_nanos();
         Ok(RuntimeStats {
-            qps: super::qps(
-                count,
-                global_stop
-                    .unwrap()
-                    .duration_since(global_start.unwrap())
-                    .as_nanos(),
-            ),
+            qps: super::qps(count, global_elapsed),
             head: global_head,
             tail: global_tail,
+            breakdown: super::ops_breakdown(&global_ops, global_elapsed),
         })
     }
 }
 
 async fn worker_svc(
     id: usize,
-    mut rx_task: broadcast::Receiver<WorkerTask<BenchmarkTask>>,
+    mut rx_task: broadcast::Receiver<WorkerTask<Workload>>,
     tx_task_result: mpsc::Sender<FuryResult<WorkerLocalStats>>,
     tx_ack: mpsc::Sender<skytable::error::Error>,
     connection_cfg: Config,
+    space: String,
 ) {
     let mut db = match connection_cfg.connect_async().await {
         Ok(c) => c,
@@ -237,7 +252,7 @@ async fn worker_svc(
     };
     // set DB in connections
     match db
-        .query_parse::<()>(&skytable::query!(format!("use {BENCHMARK_SPACE_ID}")))
+        .query_parse::<()>(&skytable::query!(format!("use {space}")))
         .await
     {
         Ok(()) => {}
@@ -250,6 +265,7 @@ async fn worker_svc(
     }
     // we're connected and ready to server
     drop(tx_ack);
+    let mut rng = StdRng::from_entropy();
     'wait: loop {
         let task = match rx_task.recv().await {
             Err(_) => {
@@ -269,9 +285,12 @@ async fn worker_svc(
         let mut local_elapsed = 0u128;
         let mut local_head = u128::MAX;
         let mut local_tail = 0u128;
+        let mut local_ops = vec![];
         while (current != 0) && !exit_now {
             // prepare query
-            let query = task.generate_query(current as _);
+            let op = task.choose(&mut rng);
+            let spec = task.op(op);
+            let query = spec.generate_query(current as _);
             // execute timed
             let start = Instant::now();
             let ret = db.query(&query).await;
@@ -294,7 +313,7 @@ async fn worker_svc(
                     continue 'wait;
                 }
             };
-            if !task.verify_response(current as _, resp.clone()) {
+            if !spec.verify_response(current as _, resp.clone()) {
                 gset_exit();
                 if tx_task_result
                     .send(Err(FuryError::Worker(FuryWorkerError::Mismatch)))
@@ -320,6 +339,7 @@ async fn worker_svc(
             if elapsed < local_head {
                 local_head = elapsed;
             }
+            super::ops_record(&mut local_ops, op, elapsed);
             current = grefresh_target();
             exit_now = grefresh_early_exit();
         }
@@ -333,6 +353,7 @@ async fn worker_svc(
                 local_elapsed,
                 local_head,
                 local_tail,
+                local_ops,
             )))
             .await
             .is_err()
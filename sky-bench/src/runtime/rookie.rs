@@ -25,7 +25,7 @@
 */
 
 use {
-    super::{RuntimeStats, WorkerLocalStats, WorkerTask},
+    super::{OpStats, RuntimeStats, WorkerLocalStats, WorkerTask},
     crossbeam_channel::{unbounded, Receiver, Sender},
     std::{
         fmt::{self, Display},
@@ -137,12 +137,15 @@ pub trait ThreadedBombardTask: Send + Sync + 'static {
     type WorkerInitError: Send + Sync;
     /// Initialize a task worker
     fn worker_init(&self) -> Result<Self::Worker, Self::WorkerInitError>;
+    /// The number of operation kinds in the spec (the stats are broken down by operation)
+    fn op_count(spec: &Self::WorkerTaskSpec) -> usize;
     fn generate_task(spec: &Self::WorkerTaskSpec, current: u64) -> Self::WorkerTask;
-    /// Drive a single subtask
+    /// Drive a single subtask, returning the kind of operation that was run (used to break down stats) along with the
+    /// time taken
     fn worker_drive_timed(
         worker: &mut Self::Worker,
         task: Self::WorkerTask,
-    ) -> Result<u128, Self::WorkerTaskError>;
+    ) -> Result<(usize, u128), Self::WorkerTaskError>;
 }
 
 /*
@@ -186,15 +189,16 @@ impl Worker {
                         let mut local_elapsed = 0u128;
                         let mut local_head = u128::MAX;
                         let mut local_tail = 0;
+                        let mut local_ops = vec![];
                         // bombard
                         while (global_position != 0) & global_okay {
                             let task = Bt::generate_task(&task, global_position);
                             if local_start.is_none() {
                                 local_start = Some(Instant::now());
                             }
-                            let this_elapsed =
+                            let (this_op, this_elapsed) =
                                 match Bt::worker_drive_timed(&mut worker_driver, task) {
-                                    Ok(elapsed) => elapsed,
+                                    Ok(ret) => ret,
                                     Err(e) => {
                                         GPState::get().post_failure();
                                         tx_res.send(WorkerResult::Errored(e)).unwrap();
@@ -208,6 +212,7 @@ impl Worker {
                             if this_elapsed > local_tail {
                                 local_tail = this_elapsed;
                             }
+                            super::ops_record(&mut local_ops, this_op, this_elapsed);
                             global_position = GPState::get().update_target();
                             global_okay = GPState::get().load_okay();
                         }
@@ -219,6 +224,7 @@ impl Worker {
                                     local_elapsed,
                                     local_head,
                                     local_tail,
+                                    local_ops,
                                 )))
                                 .unwrap();
                         }
@@ -293,6 +299,8 @@ impl<Bt: ThreadedBombardTask> BombardPool<Bt> {
             let mut global_stop = None;
             let mut global_head = u128::MAX;
             let mut global_tail = 0u128;
+            // an op that's never picked still gets (empty) stats
+            let mut global_ops = vec![OpStats::new(); Bt::op_count(&task_description)];
             for (_, sender) in self.workers.iter() {
                 sender
                     .send(WorkerTask::Task(task_description.clone()))
@@ -312,6 +320,7 @@ impl<Bt: ThreadedBombardTask> BombardPool<Bt> {
                     elapsed,
                     head,
                     tail,
+                    ops,
                 } = match results {
                     WorkerResult::Completed(r) => r,
                     WorkerResult::Errored(e) => return Err(BombardError::WorkerTaskError(e)),
@@ -346,6 +355,7 @@ impl<Bt: ThreadedBombardTask> BombardPool<Bt> {
                 if tail > global_tail {
                     global_tail = tail;
                 }
+                super::ops_merge(&mut global_ops, &ops);
                 received += 1;
             }
             // reset global pool state
@@ -359,6 +369,7 @@ impl<Bt: ThreadedBombardTask> BombardPool<Bt> {
                 qps: super::qps(count, global_elapsed),
                 head: global_head,
                 tail: global_tail,
+                breakdown: super::ops_breakdown(&global_ops, global_elapsed),
             })
         })
     }
@@ -29,8 +29,9 @@ use quote::quote;
 use {
     crate::util::{self, AttributeKind},
     proc_macro::TokenStream,
-    std::collections::HashMap,
-    syn::{parse_macro_input, AttributeArgs, ItemFn},
+    proc_macro2::{Ident, Span},
+    std::{collections::HashMap, mem},
+    syn::{parse_macro_input, parse_quote, AttributeArgs, Item, ItemFn, ItemMod},
 };
 
 /*
@@ -98,6 +99,25 @@ struct TestSetup {
     client: ClientConfig,
     db: DbConfig,
     strategy: TestStrategy,
+    expect_error: Option<proc_macro2::TokenStream>,
+    fixture: Option<syn::Path>,
+}
+
+/// Parse the expected error code which is either a numeric code or a [`QueryError`] variant name (optionally
+/// prefixed with `QueryError::`)
+fn parse_expect_error(l: &syn::Lit) -> proc_macro2::TokenStream {
+    if let Some(code) = util::extract_int_from_lit::<u16>(l) {
+        return quote!(#code);
+    }
+    let code = util::extract_str_from_lit(l).expect("`expect_error` must be an error code or name");
+    match code.parse::<u16>() {
+        Ok(code) => quote!(#code),
+        Err(_) => {
+            let variant = code.strip_prefix("QueryError::").unwrap_or(&code);
+            let variant = Ident::new(variant, Span::call_site());
+            quote!(crate::engine::error::QueryError::#variant.value_u8() as u16)
+        }
+    }
 }
 
 fn parse_attrs(attrs: AttributeArgs) -> TestSetup {
@@ -105,6 +125,8 @@ fn parse_attrs(attrs: AttributeArgs) -> TestSetup {
     let mut client_config = ClientConfig::default();
     let mut collected_attrs = HashMap::new();
     let mut strategy = TestStrategy::Standard;
+    let mut expect_error = None;
+    let mut fixture = None;
     for attr in attrs {
         match util::extract_attribute(&attr) {
             AttributeKind::Pair(k, v) => {
@@ -150,28 +172,54 @@ fn parse_attrs(attrs: AttributeArgs) -> TestSetup {
             }
         }
     }
-    for (attr_name, attr_val) in collected_attrs {
+    for (attr_name, attr_val) in &collected_attrs {
         match attr_name.as_str() {
-            "client" => match util::extract_str_from_lit(&attr_val).unwrap().as_str() {
+            "client" => match util::extract_str_from_lit(attr_val).unwrap().as_str() {
                 "skyhash" => db_config.client = DbTestClient::Skyhash,
                 "tcp" => db_config.client = DbTestClient::Tcp,
                 unknown_client => panic!("unknown client mode {unknown_client}"),
             },
-            "port" => db_config.port = util::extract_int_from_lit(&attr_val).unwrap(),
-            "host" => db_config.host = util::extract_str_from_lit(&attr_val).unwrap(),
+            "port" => db_config.port = util::extract_int_from_lit(attr_val).unwrap(),
+            "host" => db_config.host = util::extract_str_from_lit(attr_val).unwrap(),
             "username" => {
                 assert!(
                     !strategy.is_relogin(),
                     "`username` makes no sense when used with strategy `switch_user`. instead, set dbtest(switch_user(username = ...))"
                 );
-                client_config.username = util::extract_str_from_lit(&attr_val).unwrap()
+                client_config.username = util::extract_str_from_lit(attr_val).unwrap()
             }
             "password" => {
                 assert!(
                     !strategy.is_relogin(),
                     "`password` makes no sense when used with strategy `switch_user`. instead, set dbtest(switch_user(password = ...))"
                 );
-                client_config.password = util::extract_str_from_lit(&attr_val).unwrap();
+                client_config.password = util::extract_str_from_lit(attr_val).unwrap();
+            }
+            "auth" => {
+                assert!(
+                    !strategy.is_relogin(),
+                    "`auth` makes no sense when used with strategy `switch_user`. instead, set dbtest(switch_user(username = ..., password = ...))"
+                );
+                assert!(
+                    !collected_attrs.contains_key("username")
+                        && !collected_attrs.contains_key("password"),
+                    "`auth` can't be used with `username` or `password`"
+                );
+                let auth = util::extract_str_from_lit(attr_val).unwrap();
+                let (username, password) = auth
+                    .split_once(':')
+                    .expect("`auth` must be in the form `username:password`");
+                client_config.username = username.into();
+                client_config.password = password.into();
+            }
+            "expect_error" => expect_error = Some(parse_expect_error(attr_val)),
+            "fixture" => {
+                let path =
+                    util::extract_str_from_lit(attr_val).expect("`fixture` must be a string");
+                fixture = Some(
+                    syn::parse_str::<syn::Path>(&path)
+                        .expect("`fixture` must be a path to a `dbtest_fixture` function"),
+                );
             }
             unknown_attr => panic!("unknown dbtest attribute `{unknown_attr}`"),
         }
@@ -180,6 +228,8 @@ fn parse_attrs(attrs: AttributeArgs) -> TestSetup {
         client: client_config,
         db: db_config,
         strategy,
+        expect_error,
+        fixture,
     }
 }
 
@@ -194,12 +244,29 @@ pub fn dbtest(attrs: TokenStream, item: TokenStream) -> TokenStream {
             },
         db: DbConfig { client, port, host },
         strategy,
+        expect_error,
+        fixture,
     } = parse_attrs(attr_args);
 
     let function_attrs = &input_fn.attrs;
     let function_vis = &input_fn.vis;
     let function_sig = &input_fn.sig;
     let function_block = &input_fn.block;
+    let function_block = match expect_error {
+        // the body must evaluate to a client result, which must be the expected server error
+        Some(code) => quote! {
+            {
+                const __DBTEST_EXPECT_ERR: u16 = #code;
+                let __dbtest_ret = (|| -> ::core::result::Result<_, skytable::error::Error> #function_block)();
+                match __dbtest_ret {
+                    Err(skytable::error::Error::ServerError(c)) if c == __DBTEST_EXPECT_ERR => {}
+                    Err(e) => panic!("expected server error {__DBTEST_EXPECT_ERR} but got {e:?}"),
+                    Ok(_) => panic!("expected server error {__DBTEST_EXPECT_ERR} but the test succeeded"),
+                }
+            }
+        },
+        None => quote!(#function_block),
+    };
 
     let retfn = quote!(
         #(#function_attrs)* #function_vis #function_sig
@@ -261,6 +328,15 @@ pub fn dbtest(attrs: TokenStream, item: TokenStream) -> TokenStream {
             };
         }
     }
+    if let Some(fixture) = fixture {
+        block = quote! {
+            #block
+            {
+                let mut db = skytable::Config::new(#host, #port, #login_username, #login_password).connect().unwrap();
+                #fixture(&mut db);
+            }
+        };
+    }
     let mut ret_block = quote! {
         #block
         #function_block
@@ -287,3 +363,128 @@ pub fn dbtest(attrs: TokenStream, item: TokenStream) -> TokenStream {
     };
     ret.into()
 }
+
+/*
+    module setup
+*/
+
+pub fn dbtest_module(attrs: TokenStream, item: TokenStream) -> TokenStream {
+    let attr_args = parse_macro_input!(attrs as AttributeArgs);
+    let mut module = parse_macro_input!(item as ItemMod);
+    let mut setup = None;
+    let mut teardown = None;
+    for attr in attr_args {
+        let (key, value) = util::extract_attribute(&attr).into_pair();
+        let value = util::extract_str_from_lit(&value).expect("expected a function name");
+        let value = Ident::new(&value, Span::call_site());
+        match key.to_string().as_str() {
+            "setup" => {
+                assert!(setup.is_none(), "setup already set");
+                setup = Some(value);
+            }
+            "teardown" => {
+                assert!(teardown.is_none(), "teardown already set");
+                teardown = Some(value);
+            }
+            unknown_attr => panic!("unknown dbtest_module attribute `{unknown_attr}`"),
+        }
+    }
+    assert!(
+        setup.is_some() | teardown.is_some(),
+        "at least one of `setup` or `teardown` must be set"
+    );
+    let host = libsky::test_utils::DEFAULT_HOST;
+    let port = libsky::test_utils::DEFAULT_PORT;
+    let username = libsky::test_utils::DEFAULT_USER_NAME;
+    let password = libsky::test_utils::DEFAULT_USER_PASS;
+    let setup = match setup {
+        Some(setup) => quote! {
+            static __DBTEST_MODULE_SETUP: ::std::sync::Once = ::std::sync::Once::new();
+            __DBTEST_MODULE_SETUP.call_once(|| {
+                let mut db = skytable::Config::new(#host, #port, #username, #password).connect().unwrap();
+                #setup(&mut db);
+            });
+        },
+        None => quote!(),
+    };
+    let teardown = match teardown {
+        Some(teardown) => quote!(#teardown(&mut db);),
+        None => quote!(),
+    };
+    let (_, items) = module
+        .content
+        .as_mut()
+        .expect("dbtest_module can only be used on inline modules");
+    // every test in the module holds a guard for its entire run. the first one runs the setup and the last one to
+    // finish runs the teardown. the count is fixed at compile time, so a filtered run never reaches zero and skips the
+    // teardown
+    let mut test_count = 0usize;
+    for item in items.iter_mut() {
+        if let Item::Fn(f) = item {
+            if f.attrs.iter().any(|attr| attr.path.is_ident("dbtest")) {
+                f.block.stmts.insert(
+                    0,
+                    parse_quote!(let __dbtest_module_guard = __DbTestModuleGuard::enter();),
+                );
+                test_count += 1;
+            }
+        }
+    }
+    items.push(Item::Verbatim(quote! {
+        /// setup guard set by [`sky_macros::dbtest_module`]
+        struct __DbTestModuleGuard;
+        static __DBTEST_MODULE_PENDING: ::std::sync::atomic::AtomicUsize =
+            ::std::sync::atomic::AtomicUsize::new(#test_count);
+        impl __DbTestModuleGuard {
+            fn enter() -> Self {
+                #setup
+                Self
+            }
+        }
+        impl ::core::ops::Drop for __DbTestModuleGuard {
+            fn drop(&mut self) {
+                let last = __DBTEST_MODULE_PENDING.fetch_sub(1, ::std::sync::atomic::Ordering::SeqCst) == 1;
+                if last & !::std::thread::panicking() {
+                    let mut db = skytable::Config::new(#host, #port, #username, #password).connect().unwrap();
+                    #teardown
+                }
+            }
+        }
+    }));
+    quote!(#module).into()
+}
+
+/*
+    fixtures
+*/
+
+pub fn dbtest_fixture(attrs: TokenStream, item: TokenStream) -> TokenStream {
+    assert!(
+        attrs.is_empty(),
+        "dbtest_fixture does not accept any attributes"
+    );
+    let mut fixture = parse_macro_input!(item as ItemFn);
+    assert!(
+        fixture.sig.asyncness.is_none(),
+        "fixtures must be synchronous since dbtest uses the blocking client"
+    );
+    assert_eq!(
+        fixture.sig.inputs.len(),
+        1,
+        "a fixture must accept a single `&mut skytable::Connection`"
+    );
+    let attrs = mem::take(&mut fixture.attrs);
+    let vis = mem::replace(&mut fixture.vis, syn::Visibility::Inherited);
+    let name = fixture.sig.ident.clone();
+    let fixture_impl = Ident::new(&format!("__dbtest_fixture_{name}"), Span::call_site());
+    fixture.sig.ident = fixture_impl.clone();
+    quote! {
+        #(#attrs)*
+        #vis fn #name(db: &mut skytable::Connection) {
+            #fixture
+            static __DBTEST_FIXTURE: ::std::sync::Once = ::std::sync::Once::new();
+            __DBTEST_FIXTURE.call_once(|| #fixture_impl(db));
+        }
+    }
+    .into()
+}
@@ -51,6 +51,7 @@ use {
 };
 
 mod dbtest;
+mod query_builder;
 mod util;
 
 #[proc_macro_attribute]
@@ -58,6 +59,23 @@ pub fn dbtest(attrs: TokenStream, item: TokenStream) -> TokenStream {
     dbtest::dbtest(attrs, item)
 }
 
+#[proc_macro_attribute]
+/// Groups `#[dbtest]` functions in an inline module that share a common setup. `setup` (and the optional `teardown`)
+/// name functions in the module that accept a `&mut skytable::Connection`. The setup is run once before the first
+/// test in the module and the teardown is run after all the tests in the module have passed. The teardown is skipped
+/// if any test fails or if only some of the tests in the module are run (for example with a test name filter), so the
+/// setup must not assume that an earlier teardown ran
+pub fn dbtest_module(attrs: TokenStream, item: TokenStream) -> TokenStream {
+    dbtest::dbtest_module(attrs, item)
+}
+
+#[proc_macro_attribute]
+/// Defines a fixture: a function accepting a `&mut skytable::Connection` that sets up shared state for tests. Use it
+/// from any test with `#[dbtest(fixture = "path::to::fixture")]`. The fixture is run at most once per test binary
+pub fn dbtest_fixture(attrs: TokenStream, item: TokenStream) -> TokenStream {
+    dbtest::dbtest_fixture(attrs, item)
+}
+
 #[proc_macro_derive(Wrapper)]
 /// Implements necessary traits for some type `T` to make it identify as a different type but mimic the functionality
 /// as the inner type it wraps around
@@ -102,6 +120,119 @@ fn wrapper(item: DeriveInput) -> TokenStream2 {
     }
 }
 
+#[proc_macro_derive(QueryBuilder, attributes(model))]
+/// Generates a typed `{Struct}InsertBuilder` for a struct annotated with `#[model(space = "...", model = "...")]`,
+/// with a setter for every field (column). `build` runs the insert query and only compiles once all columns are set
+pub fn derive_query_builder(t: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(t as DeriveInput);
+    let r = query_builder::query_builder(item);
+    r.into()
+}
+
+#[proc_macro_derive(ErrorKind, attributes(error_kind))]
+/// Implements `From<T>` for an error enumeration, for every variant that is a single-field tuple wrapping some `T`. Use
+/// `#[error_kind(skip)]` on a variant to not generate an impl for it
+pub fn derive_error_kind(t: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(t as DeriveInput);
+    let r = error_kind(item);
+    r.into()
+}
+
+fn error_kind(item: DeriveInput) -> TokenStream2 {
+    let enum_name = &item.ident;
+    let variants = match item.data {
+        Data::Enum(ref e) => &e.variants,
+        _ => panic!("this derive macro only works on enums"),
+    };
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    let impls = variants
+        .iter()
+        .filter(|variant| !error_kind_is_skipped(&variant.attrs))
+        .filter_map(|variant| match variant.fields {
+            Fields::Unnamed(ref f) if f.unnamed.len() == 1 => {
+                Some((&variant.ident, &f.unnamed[0].ty))
+            }
+            _ => None,
+        })
+        .map(|(variant, ty)| {
+            quote! {
+                #[automatically_derived]
+                impl #impl_generics ::core::convert::From<#ty> for #enum_name #ty_generics #where_clause {
+                    fn from(v: #ty) -> Self { Self::#variant(v) }
+                }
+            }
+        });
+    quote! { #(#impls)* }
+}
+
+fn error_kind_is_skipped(attrs: &[syn::Attribute]) -> bool {
+    let mut skip = false;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("error_kind")) {
+        match attr.parse_meta().unwrap() {
+            Meta::List(list) => {
+                for nested in list.nested.iter() {
+                    match nested {
+                        NestedMeta::Meta(Meta::Path(p)) if p.is_ident("skip") => skip = true,
+                        _ => panic!("unknown error_kind attribute. expected `skip`"),
+                    }
+                }
+            }
+            _ => panic!("expected #[error_kind(skip)]"),
+        }
+    }
+    skip
+}
+
+#[proc_macro_derive(SkyCodec)]
+/// Implements `SkyEncodable` (binary encode/decode) for a struct by encoding every field in declaration order. All
+/// fields must implement `SkyEncodable`
+pub fn derive_sky_codec(t: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(t as DeriveInput);
+    let r = sky_codec(item);
+    r.into()
+}
+
+fn sky_codec(item: DeriveInput) -> TokenStream2 {
+    let st_name = &item.ident;
+    let fields = match item.data {
+        Data::Struct(DataStruct { ref fields, .. }) => fields,
+        _ => panic!("this derive macro only works on structs"),
+    };
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    let vars: Vec<_> = (0..fields.len())
+        .map(|i| syn::Ident::new(&format!("__f{i}"), proc_macro2::Span::call_site()))
+        .collect();
+    let tys = fields.iter().map(|f| &f.ty);
+    let accessors: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match f.ident {
+            Some(ref ident) => quote!(#ident),
+            None => {
+                let idx = syn::Index::from(i);
+                quote!(#idx)
+            }
+        })
+        .collect();
+    let construct = match fields {
+        Fields::Named(_) => quote!(Self { #(#accessors: #vars),* }),
+        Fields::Unnamed(_) => quote!(Self(#(#vars),*)),
+        Fields::Unit => quote!(Self),
+    };
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics crate::util::codec::SkyEncodable for #st_name #ty_generics #where_clause {
+            fn encode_to(&self, buf: &mut ::std::vec::Vec<u8>) {
+                #(crate::util::codec::SkyEncodable::encode_to(&self.#accessors, buf);)*
+            }
+            fn decode_from(buf: &[u8]) -> ::core::option::Option<(Self, &[u8])> {
+                #(let (#vars, buf) = <#tys as crate::util::codec::SkyEncodable>::decode_from(buf)?;)*
+                ::core::option::Option::Some((#construct, buf))
+            }
+        }
+    }
+}
+
 #[proc_macro_derive(TaggedEnum)]
 pub fn derive_tagged_enum(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
@@ -156,6 +287,57 @@ pub fn derive_value_methods(input: TokenStream) -> TokenStream {
     gen.into()
 }
 
+#[proc_macro_derive(ValueDisplay)]
+/// Implements `Display` for an enumeration by printing `{enum_name}({value})`, using the `value_*` method from
+/// `EnumMethods` (which must also be derived)
+pub fn derive_value_display(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let (enum_name, repr_type, _, _, _) = process_enum_tags(&ast);
+    let repr_type_ident_func = syn::Ident::new(
+        &format!("value_{repr_type}"),
+        proc_macro2::Span::call_site(),
+    );
+    let name = enum_name.to_string();
+    quote! {
+        #[automatically_derived]
+        impl ::core::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                write!(f, "{}({})", #name, self.#repr_type_ident_func())
+            }
+        }
+    }
+    .into()
+}
+
+#[proc_macro_derive(DisplayEnum)]
+/// Implements `Display` for an enumeration of unit variants by printing the name of the variant
+pub fn derive_display_enum(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let enum_name = &ast.ident;
+    let variants = match ast.data {
+        Data::Enum(ref e) => &e.variants,
+        _ => panic!("this derive macro only works on enums"),
+    };
+    let arms = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = ident.to_string();
+        match variant.fields {
+            Fields::Unit => quote!(Self::#ident => #name),
+            _ => panic!("All enum variants must be unit variants"),
+        }
+    });
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::fmt::Display for #enum_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(match self { #(#arms),* })
+            }
+        }
+    }
+    .into()
+}
+
 fn process_enum_tags(
     ast: &DeriveInput,
 ) -> (
@@ -0,0 +1,142 @@
+/*
+ * Created on Sat Oct 17 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+use {
+    crate::util::{self, AttributeKind},
+    proc_macro2::{Ident, Span, TokenStream as TokenStream2},
+    quote::quote,
+    syn::{Data, DataStruct, DeriveInput, Fields, Meta},
+};
+
+/// Returns the `(space, model)` set using `#[model(space = "...", model = "...")]`
+fn model_attrs(attrs: &[syn::Attribute]) -> (String, String) {
+    let mut space = None;
+    let mut model = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("model")) {
+        let list = match attr.parse_meta().unwrap() {
+            Meta::List(list) => list,
+            _ => panic!("expected #[model(space = \"...\", model = \"...\")]"),
+        };
+        for nested in list.nested.iter() {
+            let (key, value) = match util::extract_attribute(nested) {
+                AttributeKind::Pair(key, value) => (key, value),
+                _ => panic!("expected #[model(space = \"...\", model = \"...\")]"),
+            };
+            let target = match key.to_string().as_str() {
+                "space" => &mut space,
+                "model" => &mut model,
+                unknown => panic!("unknown model attribute `{unknown}`"),
+            };
+            assert!(target.is_none(), "duplicate key: {key}");
+            *target = Some(util::extract_str_from_lit(&value).expect("expected a string"));
+        }
+    }
+    (
+        space.expect("`space` must be set using #[model(space = \"...\")]"),
+        model.expect("`model` must be set using #[model(model = \"...\")]"),
+    )
+}
+
+pub fn query_builder(item: DeriveInput) -> TokenStream2 {
+    let st_name = &item.ident;
+    let st_vis = &item.vis;
+    assert!(
+        item.generics.params.is_empty(),
+        "generic structs are not supported"
+    );
+    let fields = match item.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(ref f),
+            ..
+        }) if !f.named.is_empty() => &f.named,
+        _ => panic!("only works on structs with named fields"),
+    };
+    let (space, model) = model_attrs(&item.attrs);
+    let builder = Ident::new(&format!("{st_name}InsertBuilder"), Span::call_site());
+    let names: Vec<_> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+    let types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+    // every field is tracked with a const generic, so that `build` is only available once all columns are set
+    let flags: Vec<_> = (0..names.len())
+        .map(|i| Ident::new(&format!("__F{i}"), Span::call_site()))
+        .collect();
+    let unset = flags.iter().map(|_| quote!(false));
+    let all_set = flags.iter().map(|_| quote!(true));
+    let query = format!(
+        "insert into {space}.{model} {{ {} }}",
+        names
+            .iter()
+            .map(|name| format!("{name}: ?"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let doc = format!(
+        "Typed insert query builder for [`{st_name}`]. `build` is only available once all columns are set"
+    );
+    let setters = names
+        .iter()
+        .zip(types.iter())
+        .enumerate()
+        .map(|(i, (name, ty))| {
+            let ret_flags =
+                flags.iter().enumerate().map(
+                    |(j, flag)| {
+                        if i == j {
+                            quote!(true)
+                        } else {
+                            quote!(#flag)
+                        }
+                    },
+                );
+            let others = names.iter().filter(|other| *other != name);
+            quote! {
+                pub fn #name(self, #name: #ty) -> #builder<#(#ret_flags),*> {
+                    #builder { #name: Some(#name), #(#others: self.#others),* }
+                }
+            }
+        });
+    quote! {
+        #[automatically_derived]
+        #[doc = #doc]
+        #st_vis struct #builder<#(const #flags: bool),*> { #(#names: Option<#types>),* }
+        #[automatically_derived]
+        impl #st_name {
+            pub fn insert_builder() -> #builder<#(#unset),*> {
+                #builder { #(#names: None),* }
+            }
+        }
+        #[automatically_derived]
+        impl<#(const #flags: bool),*> #builder<#(#flags),*> {
+            #(#setters)*
+        }
+        #[automatically_derived]
+        impl #builder<#(#all_set),*> {
+            /// Run the insert query using the given connection
+            pub fn build(self, db: &mut skytable::Connection) -> skytable::error::ClientResult<()> {
+                db.query_parse::<()>(&skytable::query!(#query, #(self.#names.unwrap()),*))
+            }
+        }
+    }
+}